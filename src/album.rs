@@ -0,0 +1,47 @@
+//! # Album
+//!
+//! defines the [`Album`] struct and its methods.
+use crate::serde_helpers::empty_string_as_none;
+use serde::{Deserialize, Serialize};
+
+/// A Last.fm album, as referenced by a track.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Album {
+    #[serde(rename = "#text")]
+    pub name: String,
+    /// The MusicBrainz ID of the album (if known).
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub mbid: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn it_deserializes_correctly() {
+        let json_value = json!({
+          "mbid": "3b83450b-ca91-46b2-af88-ff285e038d23",
+          "#text": "Do You Feel"
+        });
+
+        let album: Album = serde_json::from_value(json_value).unwrap();
+        assert_eq!(album.name, "Do You Feel");
+        assert_eq!(
+            album.mbid,
+            Some("3b83450b-ca91-46b2-af88-ff285e038d23".to_string())
+        );
+    }
+
+    #[test]
+    fn it_normalises_empty_mbid() {
+        let json_value = json!({
+          "mbid": "",
+          "#text": "Solo cose belle"
+        });
+
+        let album: Album = serde_json::from_value(json_value).unwrap();
+        assert_eq!(album.mbid, None);
+    }
+}
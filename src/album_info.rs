@@ -0,0 +1,241 @@
+//! # Album info
+//!
+//! Defines the [`AlbumQuery`], [`AlbumInfo`] and [`AlbumTrack`] structs and the method
+//! to fetch the metadata of an album (`album.getInfo`).
+//!
+//! ```rust,no_run
+//! # use lastfm::{album_info::AlbumQuery, Client};
+//! #
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!   let client = Client::builder().api_key("YOUR_API_KEY").username("YOUR_USERNAME").build();
+//!   let album = client
+//!     .album_info(&AlbumQuery::by_name("Comete", "Solo cose belle"))
+//!     .await?;
+//!   for track in album.tracks {
+//!     println!("{}. {}", track.rank, track.name);
+//!   }
+//!
+//!   Ok(())
+//! }
+//! ```
+use crate::{
+    errors::Error,
+    imageset::ImageSet,
+    request::{Auth, Params, QueryOptions},
+    serde_helpers::{
+        empty_string_as_none, number_from_any, one_or_many, option_number_from_any, rank_from_attr,
+    },
+    tag::{tags_from_any, Tag},
+    track::RecordedTrack,
+    wiki::Wiki,
+    Client,
+};
+use serde::{Deserialize, Serialize};
+
+/// How an album is looked up.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum AlbumLookup {
+    Name { artist: String, album: String },
+    Mbid(String),
+}
+
+/// Identifies the album of an `album.*` call, either by artist and album name or by MusicBrainz ID.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlbumQuery {
+    lookup: AlbumLookup,
+    options: QueryOptions,
+}
+
+impl AlbumQuery {
+    /// Looks up the album by artist and album name.
+    pub fn by_name(artist: impl Into<String>, album: impl Into<String>) -> Self {
+        AlbumQuery {
+            lookup: AlbumLookup::Name {
+                artist: artist.into(),
+                album: album.into(),
+            },
+            options: QueryOptions::default(),
+        }
+    }
+
+    /// Looks up the album by MusicBrainz ID.
+    pub fn by_mbid(mbid: impl Into<String>) -> Self {
+        AlbumQuery {
+            lookup: AlbumLookup::Mbid(mbid.into()),
+            options: QueryOptions::default(),
+        }
+    }
+
+    /// Whether Last.fm should correct misspelled artist names.
+    pub fn autocorrect(mut self, autocorrect: bool) -> Self {
+        self.options.autocorrect = autocorrect;
+        self
+    }
+
+    /// Includes the statistics of the given user (e.g. [`AlbumInfo::user_playcount`]) in the
+    /// `album.getInfo` response.
+    pub fn username(mut self, username: impl Into<String>) -> Self {
+        self.options.username = Some(username.into());
+        self
+    }
+
+    /// The parameters identifying the album.
+    pub(crate) fn params(&self) -> Params {
+        let params = match &self.lookup {
+            AlbumLookup::Name { artist, album } => {
+                Params::new().with("artist", artist).with("album", album)
+            }
+            AlbumLookup::Mbid(mbid) => Params::new().with("mbid", mbid),
+        };
+        self.options.with_autocorrect(params)
+    }
+
+    /// The parameters of an `album.getInfo` request, including the username (if set).
+    fn info_params(&self) -> Params {
+        self.options.with_username(self.params())
+    }
+}
+
+impl From<&RecordedTrack> for AlbumQuery {
+    /// Looks up the album of a scrobbled track, by MusicBrainz ID when known.
+    fn from(track: &RecordedTrack) -> Self {
+        match &track.album.mbid {
+            Some(mbid) => AlbumQuery::by_mbid(mbid),
+            None => AlbumQuery::by_name(&track.artist.name, &track.album.name),
+        }
+    }
+}
+
+/// A track in the tracklist of an album.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlbumTrack {
+    pub name: String,
+    pub url: String,
+    /// The length of the track in seconds (if known).
+    #[serde(default, deserialize_with = "option_number_from_any")]
+    pub duration: Option<u32>,
+    /// The position of the track in the album (starting from 1).
+    #[serde(rename(deserialize = "@attr"), deserialize_with = "rank_from_attr")]
+    pub rank: u32,
+}
+
+/// The metadata of an album (`album.getInfo`).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "RawAlbumInfo")]
+pub struct AlbumInfo {
+    pub name: String,
+    /// The name of the artist.
+    pub artist: String,
+    pub url: String,
+    /// The MusicBrainz ID of the album (if known).
+    pub mbid: Option<String>,
+    pub image: ImageSet,
+    pub listeners: u64,
+    pub playcount: u64,
+    /// How many times the user listened to the album (only if set with [`AlbumQuery::username`]).
+    pub user_playcount: Option<u64>,
+    /// The tracklist, in album order.
+    pub tracks: Vec<AlbumTrack>,
+    /// The top tags of the album.
+    pub tags: Vec<Tag>,
+    pub wiki: Option<Wiki>,
+}
+
+#[derive(Deserialize)]
+struct RawAlbumInfo {
+    name: String,
+    artist: String,
+    url: String,
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    mbid: Option<String>,
+    #[serde(default)]
+    image: ImageSet,
+    #[serde(deserialize_with = "number_from_any")]
+    listeners: u64,
+    #[serde(deserialize_with = "number_from_any")]
+    playcount: u64,
+    #[serde(default, deserialize_with = "option_number_from_any")]
+    userplaycount: Option<u64>,
+    #[serde(default)]
+    tracks: Option<RawTracks>,
+    #[serde(default, deserialize_with = "tags_from_any")]
+    tags: Vec<Tag>,
+    #[serde(default)]
+    wiki: Option<Wiki>,
+}
+
+#[derive(Deserialize)]
+struct RawTracks {
+    #[serde(default, deserialize_with = "one_or_many")]
+    track: Vec<AlbumTrack>,
+}
+
+impl From<RawAlbumInfo> for AlbumInfo {
+    fn from(raw: RawAlbumInfo) -> Self {
+        AlbumInfo {
+            name: raw.name,
+            artist: raw.artist,
+            url: raw.url,
+            mbid: raw.mbid,
+            image: raw.image,
+            listeners: raw.listeners,
+            playcount: raw.playcount,
+            user_playcount: raw.userplaycount,
+            tracks: raw.tracks.map(|t| t.track).unwrap_or_default(),
+            tags: raw.tags,
+            wiki: raw.wiki,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct AlbumInfoResponse {
+    album: AlbumInfo,
+}
+
+impl<A: AsRef<str>, U: AsRef<str>> Client<A, U> {
+    /// Fetches the metadata of an album, including its tracklist (`album.getInfo`).
+    ///
+    /// [`AlbumInfo::user_playcount`] is only available when a user is set with [`AlbumQuery::username`].
+    pub async fn album_info(&self, query: &AlbumQuery) -> Result<AlbumInfo, Error> {
+        let params = query.info_params();
+        let res: AlbumInfoResponse = self
+            .transport()
+            .get("album.getInfo", params, Auth::None)
+            .await?;
+
+        Ok(res.album)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_deserializes_album_info() {
+        let json_data = include_str!("fixtures/album_info.json");
+        let res: AlbumInfoResponse = serde_json::from_str(json_data).unwrap();
+        insta::assert_debug_snapshot!(res.album);
+    }
+
+    #[test]
+    fn it_deserializes_album_info_without_tags() {
+        let json_value = serde_json::json!({
+          "album": {
+            "artist": "Comete",
+            "tags": "",
+            "name": "Solo cose belle",
+            "url": "https://www.last.fm/music/Comete/Solo+cose+belle",
+            "listeners": "98312",
+            "playcount": "1510329"
+          }
+        });
+
+        let res: AlbumInfoResponse = serde_json::from_value(json_value).unwrap();
+        assert!(res.album.tags.is_empty());
+        assert!(res.album.tracks.is_empty());
+        assert_eq!(res.album.user_playcount, None);
+    }
+}
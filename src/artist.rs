@@ -1,13 +1,100 @@
 //! # Artist
 //!
 //! defines the [`Artist`] struct and its methods.
-use crate::imageset::ImageSet;
+use crate::{imageset::ImageSet, serde_helpers::empty_string_as_none};
 use serde::{Deserialize, Serialize};
 
 /// A Last.fm artist.
+///
+/// Depending on the endpoint (e.g. non-extended recent tracks), Last.fm might only return
+/// the name and the MusicBrainz ID of the artist. In that case `url` is empty and `image` has no images.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "RawArtist")]
 pub struct Artist {
     pub image: ImageSet,
     pub name: String,
     pub url: String,
+    /// The MusicBrainz ID of the artist (if known).
+    pub mbid: Option<String>,
+}
+
+/// The different shapes an artist can have in a Last.fm response.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawArtist {
+    Extended {
+        #[serde(default)]
+        image: ImageSet,
+        name: String,
+        url: String,
+        #[serde(default, deserialize_with = "empty_string_as_none")]
+        mbid: Option<String>,
+    },
+    Compact {
+        #[serde(rename = "#text")]
+        name: String,
+        #[serde(default, deserialize_with = "empty_string_as_none")]
+        mbid: Option<String>,
+    },
+}
+
+impl From<RawArtist> for Artist {
+    fn from(raw: RawArtist) -> Self {
+        match raw {
+            RawArtist::Extended {
+                image,
+                name,
+                url,
+                mbid,
+            } => Artist {
+                image,
+                name,
+                url,
+                mbid,
+            },
+            RawArtist::Compact { name, mbid } => Artist {
+                image: ImageSet::default(),
+                name,
+                url: String::new(),
+                mbid,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn it_deserializes_an_extended_artist() {
+        let json_value = json!({
+          "url": "https://www.last.fm/music/Comete",
+          "name": "Comete",
+          "image": [],
+          "mbid": ""
+        });
+
+        let artist: Artist = serde_json::from_value(json_value).unwrap();
+        assert_eq!(artist.name, "Comete");
+        assert_eq!(artist.url, "https://www.last.fm/music/Comete");
+        assert_eq!(artist.mbid, None);
+    }
+
+    #[test]
+    fn it_deserializes_a_compact_artist() {
+        let json_value = json!({
+          "mbid": "6f1a58bf-9417-4bf1-8b6d-0d4e8a5e9c20",
+          "#text": "Editors"
+        });
+
+        let artist: Artist = serde_json::from_value(json_value).unwrap();
+        assert_eq!(artist.name, "Editors");
+        assert_eq!(artist.url, "");
+        assert_eq!(
+            artist.mbid,
+            Some("6f1a58bf-9417-4bf1-8b6d-0d4e8a5e9c20".to_string())
+        );
+    }
 }
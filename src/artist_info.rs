@@ -0,0 +1,392 @@
+//! # Artist info
+//!
+//! Defines the [`ArtistQuery`], [`ArtistInfo`] and [`SimilarArtist`] structs and the methods
+//! to fetch the metadata of an artist (`artist.*`).
+//!
+//! ```rust,no_run
+//! use futures_util::pin_mut;
+//! use futures_util::stream::StreamExt;
+//! # use lastfm::{artist_info::ArtistQuery, Client};
+//! #
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!   let client = Client::builder().api_key("YOUR_API_KEY").username("YOUR_USERNAME").build();
+//!   let query = ArtistQuery::by_name("Comete")
+//!     .autocorrect(true)
+//!     .username("YOUR_USERNAME");
+//!
+//!   let info = client.artist_info(&query).await?;
+//!   println!("{}: {} listeners, {:?} plays by you", info.name, info.listeners, info.user_playcount);
+//!
+//!   for similar in client.similar_artists(&query, 10).await? {
+//!     println!("{} ({:.2})", similar.artist.name, similar.match_score);
+//!   }
+//!
+//!   let top_tracks = client.artist_top_tracks(&query).await?.into_stream();
+//!   pin_mut!(top_tracks);
+//!   while let Some(track) = top_tracks.next().await {
+//!     let track = track?;
+//!     println!("{}. {}", track.rank, track.name);
+//!   }
+//!
+//!   Ok(())
+//! }
+//! ```
+use crate::{
+    artist::Artist,
+    errors::Error,
+    imageset::ImageSet,
+    pagination::{PageFetcher, PageRequest},
+    request::{Auth, Params, QueryOptions},
+    serde_helpers::{
+        bool_from_any, empty_string_as_none, items_under, number_from_any, one_or_many,
+        option_number_from_any,
+    },
+    tag::{tags_from_any, Tag},
+    top::{TopAlbum, TopTrack},
+    wiki::Wiki,
+    Client,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// How an artist is looked up.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum ArtistLookup {
+    Name(String),
+    Mbid(String),
+}
+
+/// Identifies the artist of an `artist.*` call, either by name or by MusicBrainz ID.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArtistQuery {
+    lookup: ArtistLookup,
+    options: QueryOptions,
+}
+
+impl ArtistQuery {
+    /// Looks up the artist by name.
+    pub fn by_name(name: impl Into<String>) -> Self {
+        ArtistQuery {
+            lookup: ArtistLookup::Name(name.into()),
+            options: QueryOptions::default(),
+        }
+    }
+
+    /// Looks up the artist by MusicBrainz ID.
+    pub fn by_mbid(mbid: impl Into<String>) -> Self {
+        ArtistQuery {
+            lookup: ArtistLookup::Mbid(mbid.into()),
+            options: QueryOptions::default(),
+        }
+    }
+
+    /// Whether Last.fm should correct misspelled artist names (e.g. `the beatles` -> `The Beatles`).
+    pub fn autocorrect(mut self, autocorrect: bool) -> Self {
+        self.options.autocorrect = autocorrect;
+        self
+    }
+
+    /// Includes the statistics of the given user (e.g. [`ArtistInfo::user_playcount`]) in the
+    /// `artist.getInfo` response.
+    pub fn username(mut self, username: impl Into<String>) -> Self {
+        self.options.username = Some(username.into());
+        self
+    }
+
+    /// The parameters identifying the artist.
+    pub(crate) fn params(&self) -> Params {
+        let params = match &self.lookup {
+            ArtistLookup::Name(name) => Params::new().with("artist", name),
+            ArtistLookup::Mbid(mbid) => Params::new().with("mbid", mbid),
+        };
+        self.options.with_autocorrect(params)
+    }
+
+    /// The parameters of an `artist.getInfo` request, including the username (if set).
+    fn info_params(&self) -> Params {
+        self.options.with_username(self.params())
+    }
+}
+
+impl From<&str> for ArtistQuery {
+    fn from(name: &str) -> Self {
+        ArtistQuery::by_name(name)
+    }
+}
+
+impl From<&Artist> for ArtistQuery {
+    /// Looks up the artist by MusicBrainz ID when known, by name otherwise.
+    fn from(artist: &Artist) -> Self {
+        match &artist.mbid {
+            Some(mbid) => ArtistQuery::by_mbid(mbid),
+            None => ArtistQuery::by_name(&artist.name),
+        }
+    }
+}
+
+/// The metadata of an artist (`artist.getInfo`).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "RawArtistInfo")]
+pub struct ArtistInfo {
+    pub name: String,
+    pub url: String,
+    /// The MusicBrainz ID of the artist (if known).
+    pub mbid: Option<String>,
+    pub image: ImageSet,
+    /// Whether the artist is currently on tour.
+    pub on_tour: bool,
+    pub listeners: u64,
+    pub playcount: u64,
+    /// How many times the user listened to the artist (only if set with [`ArtistQuery::username`]).
+    pub user_playcount: Option<u64>,
+    /// A few similar artists (use [`Client::similar_artists`] for the full list with match scores).
+    pub similar: Vec<Artist>,
+    /// The top tags of the artist.
+    pub tags: Vec<Tag>,
+    pub bio: Option<Wiki>,
+}
+
+#[derive(Deserialize)]
+struct RawArtistInfo {
+    name: String,
+    url: String,
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    mbid: Option<String>,
+    #[serde(default)]
+    image: ImageSet,
+    #[serde(default, deserialize_with = "bool_from_any")]
+    ontour: bool,
+    stats: RawStats,
+    #[serde(default)]
+    similar: Option<RawSimilar>,
+    #[serde(default, deserialize_with = "tags_from_any")]
+    tags: Vec<Tag>,
+    #[serde(default)]
+    bio: Option<Wiki>,
+}
+
+#[derive(Deserialize)]
+struct RawStats {
+    #[serde(deserialize_with = "number_from_any")]
+    listeners: u64,
+    #[serde(deserialize_with = "number_from_any")]
+    playcount: u64,
+    #[serde(default, deserialize_with = "option_number_from_any")]
+    userplaycount: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct RawSimilar {
+    #[serde(default, deserialize_with = "one_or_many")]
+    artist: Vec<Artist>,
+}
+
+impl From<RawArtistInfo> for ArtistInfo {
+    fn from(raw: RawArtistInfo) -> Self {
+        ArtistInfo {
+            name: raw.name,
+            url: raw.url,
+            mbid: raw.mbid,
+            image: raw.image,
+            on_tour: raw.ontour,
+            listeners: raw.stats.listeners,
+            playcount: raw.stats.playcount,
+            user_playcount: raw.stats.userplaycount,
+            similar: raw.similar.map(|s| s.artist).unwrap_or_default(),
+            tags: raw.tags,
+            bio: raw.bio,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ArtistInfoResponse {
+    artist: ArtistInfo,
+}
+
+/// An artist similar to another one (`artist.getSimilar`).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct SimilarArtist {
+    #[serde(flatten)]
+    pub artist: Artist,
+    /// How similar the artist is, from `0.0` to `1.0`.
+    #[serde(rename = "match", deserialize_with = "number_from_any")]
+    pub match_score: f64,
+}
+
+/// Structs that can be used to get a stream of the [`TopTrack`]s of an artist.
+pub type ArtistTopTracksFetcher = PageFetcher<TopTrack>;
+
+/// Structs that can be used to get a stream of the [`TopAlbum`]s of an artist.
+pub type ArtistTopAlbumsFetcher = PageFetcher<TopAlbum>;
+
+impl<A: AsRef<str>, U: AsRef<str>> Client<A, U> {
+    /// Fetches the metadata of an artist (`artist.getInfo`).
+    ///
+    /// [`ArtistInfo::user_playcount`] is only available when a user is set with [`ArtistQuery::username`].
+    pub async fn artist_info(&self, query: &ArtistQuery) -> Result<ArtistInfo, Error> {
+        let params = query.info_params();
+        let res: ArtistInfoResponse = self
+            .transport()
+            .get("artist.getInfo", params, Auth::None)
+            .await?;
+
+        Ok(res.artist)
+    }
+
+    /// Fetches up to `limit` artists similar to the given one, most similar first (`artist.getSimilar`).
+    pub async fn similar_artists(
+        &self,
+        query: &ArtistQuery,
+        limit: u32,
+    ) -> Result<Vec<SimilarArtist>, Error> {
+        let params = query.params().with("limit", limit);
+        let raw: Value = self
+            .transport()
+            .get("artist.getSimilar", params, Auth::None)
+            .await?;
+
+        Ok(items_under(&raw, "similarartists", "artist")?)
+    }
+
+    /// Fetches the top tags of an artist (`artist.getTopTags`).
+    pub async fn artist_top_tags(&self, query: &ArtistQuery) -> Result<Vec<Tag>, Error> {
+        let raw: Value = self
+            .transport()
+            .get("artist.getTopTags", query.params(), Auth::None)
+            .await?;
+
+        Ok(items_under(&raw, "toptags", "tag")?)
+    }
+
+    /// Creates a new [`ArtistTopTracksFetcher`] that can be used to fetch the most listened tracks
+    /// of an artist (`artist.getTopTracks`).
+    pub async fn artist_top_tracks(
+        &self,
+        query: &ArtistQuery,
+    ) -> Result<ArtistTopTracksFetcher, Error> {
+        PageFetcher::new(
+            self.transport(),
+            PageRequest {
+                method: "artist.getTopTracks",
+                params: query.params().with("limit", 200),
+                root: "toptracks",
+                item: "track",
+            },
+        )
+        .await
+    }
+
+    /// Creates a new [`ArtistTopAlbumsFetcher`] that can be used to fetch the most listened albums
+    /// of an artist (`artist.getTopAlbums`).
+    pub async fn artist_top_albums(
+        &self,
+        query: &ArtistQuery,
+    ) -> Result<ArtistTopAlbumsFetcher, Error> {
+        PageFetcher::new(
+            self.transport(),
+            PageRequest {
+                method: "artist.getTopAlbums",
+                params: query.params().with("limit", 200),
+                root: "topalbums",
+                item: "album",
+            },
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn it_deserializes_artist_info() {
+        let json_data = include_str!("fixtures/artist_info.json");
+        let res: ArtistInfoResponse = serde_json::from_str(json_data).unwrap();
+        insta::assert_debug_snapshot!(res.artist);
+    }
+
+    #[test]
+    fn it_deserializes_similar_artists() {
+        let raw = json!({
+          "similarartists": {
+            "artist": [
+              {
+                "name": "Tommaso Paradiso",
+                "mbid": "",
+                "match": "1",
+                "url": "https://www.last.fm/music/Tommaso+Paradiso",
+                "image": [],
+                "streamable": "0"
+              },
+              {
+                "name": "Calcutta",
+                "mbid": "",
+                "match": "0.874312",
+                "url": "https://www.last.fm/music/Calcutta",
+                "image": [],
+                "streamable": "0"
+              }
+            ],
+            "@attr": { "artist": "Comete" }
+          }
+        });
+
+        let similar: Vec<SimilarArtist> = items_under(&raw, "similarartists", "artist").unwrap();
+        assert_eq!(similar.len(), 2);
+        assert_eq!(similar[0].artist.name, "Tommaso Paradiso");
+        assert_eq!(similar[0].match_score, 1.0);
+        assert_eq!(similar[1].match_score, 0.874312);
+    }
+
+    #[test]
+    fn it_deserializes_unranked_top_albums() {
+        let raw = json!({
+          "topalbums": {
+            "album": {
+              "name": "Solo cose belle",
+              "playcount": 1510329,
+              "url": "https://www.last.fm/music/Comete/Solo+cose+belle",
+              "artist": { "name": "Comete", "mbid": "", "url": "https://www.last.fm/music/Comete" },
+              "image": []
+            },
+            "@attr": { "artist": "Comete", "page": "1", "perPage": "50", "totalPages": "1", "total": "1" }
+          }
+        });
+
+        let albums: Vec<TopAlbum> = items_under(&raw, "topalbums", "album").unwrap();
+        assert_eq!(albums[0].rank, 0);
+        assert_eq!(albums[0].playcount, 1510329);
+    }
+
+    #[test]
+    fn it_builds_query_params() {
+        let params = ArtistQuery::by_name("comete").autocorrect(true).params();
+        assert_eq!(
+            params.0,
+            vec![
+                ("artist".to_string(), "comete".to_string()),
+                ("autocorrect".to_string(), "1".to_string())
+            ]
+        );
+
+        let artist = Artist {
+            image: ImageSet::default(),
+            name: "Editors".to_string(),
+            url: String::new(),
+            mbid: Some("6f1a58bf-9417-4bf1-8b6d-0d4e8a5e9c20".to_string()),
+        };
+        let params = ArtistQuery::from(&artist).params();
+        assert!(!params.0.iter().any(|(k, _)| k == "username"));
+        assert_eq!(
+            params.0[0],
+            (
+                "mbid".to_string(),
+                "6f1a58bf-9417-4bf1-8b6d-0d4e8a5e9c20".to_string()
+            )
+        );
+    }
+}
@@ -0,0 +1,171 @@
+//! # Auth
+//!
+//! Implements the Last.fm web/desktop authentication flow.
+//!
+//! The flow happens in three steps:
+//!
+//! 1. fetch a request token with [`Client::get_token`]
+//! 2. send the user to the URL returned by [`Client::auth_url`] so they can grant access to your application
+//! 3. exchange the (now authorized) token for a [`Session`] with [`Client::get_session`]
+//!
+//! The resulting [`Session::key`] can then be passed to [`ClientBuilder::session_key`](crate::ClientBuilder)
+//! to perform authenticated calls.
+//!
+//! ```rust,no_run
+//! # use lastfm::Client;
+//! #
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!   let client = Client::builder()
+//!     .api_key("YOUR_API_KEY")
+//!     .username("YOUR_USERNAME")
+//!     .api_secret("YOUR_API_SECRET")
+//!     .build();
+//!
+//!   let token = client.get_token().await?;
+//!   println!("Authorize the application at: {}", client.auth_url(&token));
+//!   // ... wait for the user to authorize the application
+//!   let session = client.get_session(&token).await?;
+//!   println!("Session key for {}: {}", session.name, session.key);
+//!
+//!   Ok(())
+//! }
+//! ```
+//!
+//! See <https://www.last.fm/api/desktopauth> for more details.
+//!
+//! Headless services and CLI tools can instead use [`Client::login`], which exchanges
+//! a username and password for a session (`auth.getMobileSession`).
+use crate::{
+    errors::Error,
+    request::{Auth, Params},
+    serde_helpers::bool_from_any,
+    Client,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// The base URL of the page where users authorize an application.
+pub const AUTH_URL: &str = "https://www.last.fm/api/auth/";
+
+/// A request token obtained through `auth.getToken`.
+///
+/// A token is valid for 60 minutes and can only be used once to create a [`Session`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Token {
+    pub token: String,
+}
+
+/// An authenticated Last.fm session.
+///
+/// Session keys have an infinite lifetime by default, so they can be stored and reused.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Session {
+    /// The name of the authenticated user.
+    pub name: String,
+    /// The session key to use for authenticated calls.
+    pub key: String,
+    /// Whether the user is a Last.fm subscriber.
+    #[serde(deserialize_with = "bool_from_any")]
+    pub subscriber: bool,
+}
+
+/// The response of the `auth.getSession` and `auth.getMobileSession` methods.
+#[derive(Deserialize)]
+pub(crate) struct SessionResponse {
+    pub(crate) session: Session,
+}
+
+impl<A: AsRef<str>, U: AsRef<str>> Client<A, U> {
+    /// Fetches a new request token (`auth.getToken`).
+    ///
+    /// Requires the client to be configured with an `api_secret`.
+    pub async fn get_token(&self) -> Result<Token, Error> {
+        self.transport()
+            .get("auth.getToken", Params::new(), Auth::Signed)
+            .await
+    }
+
+    /// Builds the URL where the user can authorize the application to use the given token.
+    pub fn auth_url(&self, token: &Token) -> Url {
+        Url::parse_with_params(
+            AUTH_URL,
+            &[("api_key", self.api_key()), ("token", &token.token)],
+        )
+        .unwrap()
+    }
+
+    /// Exchanges an authorized token for a [`Session`] (`auth.getSession`).
+    ///
+    /// Requires the client to be configured with an `api_secret`.
+    /// If the user has not authorized the token yet, this returns [`Error::UnauthorizedToken`].
+    pub async fn get_session(&self, token: &Token) -> Result<Session, Error> {
+        let params = Params::new().with("token", &token.token);
+        let res: SessionResponse = self
+            .transport()
+            .get("auth.getSession", params, Auth::Signed)
+            .await?;
+
+        Ok(res.session)
+    }
+
+    /// Logs in with the given username and password (`auth.getMobileSession`).
+    ///
+    /// On success, the session key is stored on the client, so that it can be used straight away
+    /// for authenticated calls. Requires the client to be configured with an `api_secret`.
+    pub async fn login(&mut self, username: &str, password: &str) -> Result<Session, Error> {
+        let params = Params::new()
+            .with("username", username)
+            .with("password", password);
+        let res: SessionResponse = self
+            .transport()
+            .post("auth.getMobileSession", params, Auth::Signed)
+            .await?;
+
+        self.set_session_key(&res.session.key);
+        Ok(res.session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn it_deserializes_a_session() {
+        let json_value = json!({
+          "session": {
+            "name": "loige",
+            "key": "d580d57f32848f5dcf574d1ce18d78b2",
+            "subscriber": 0
+          }
+        });
+
+        let res: SessionResponse = serde_json::from_value(json_value).unwrap();
+        assert_eq!(
+            res.session,
+            Session {
+                name: "loige".to_string(),
+                key: "d580d57f32848f5dcf574d1ce18d78b2".to_string(),
+                subscriber: false,
+            }
+        );
+    }
+
+    #[test]
+    fn it_builds_the_auth_url() {
+        let client = Client::builder()
+            .api_key("some-api-key")
+            .username("loige")
+            .build();
+        let token = Token {
+            token: "some-token".to_string(),
+        };
+
+        assert_eq!(
+            client.auth_url(&token).as_str(),
+            "https://www.last.fm/api/auth/?api_key=some-api-key&token=some-token"
+        );
+    }
+}
@@ -0,0 +1,171 @@
+//! # Charts
+//!
+//! Defines the methods to fetch the global Last.fm charts (`chart.*`) and the charts
+//! of a single country (`geo.*`).
+//!
+//! ```rust,no_run
+//! use futures_util::pin_mut;
+//! use futures_util::stream::StreamExt;
+//! # use lastfm::{country::Country, Client};
+//! #
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!   let client = Client::builder().api_key("YOUR_API_KEY").username("YOUR_USERNAME").build();
+//!   let country: Country = "IT".parse()?;
+//!   let top_tracks = client.geo_top_tracks(country).await?.into_stream().take(10);
+//!   pin_mut!(top_tracks);
+//!   while let Some(track) = top_tracks.next().await {
+//!     let track = track?;
+//!     println!("{} - {}", track.artist.name, track.name);
+//!   }
+//!
+//!   Ok(())
+//! }
+//! ```
+use crate::{
+    country::Country,
+    errors::Error,
+    pagination::{PageFetcher, PageRequest},
+    request::Params,
+    tag_info::TagInfo,
+    top::{TopArtist, TopTrack},
+    Client,
+};
+
+/// Structs that can be used to get a stream of the [`TopArtist`]s of a chart.
+pub type ChartTopArtistsFetcher = PageFetcher<TopArtist>;
+
+/// Structs that can be used to get a stream of the [`TopTrack`]s of a chart.
+pub type ChartTopTracksFetcher = PageFetcher<TopTrack>;
+
+/// Structs that can be used to get a stream of the [`TagInfo`]s of a chart.
+pub type ChartTopTagsFetcher = PageFetcher<TagInfo>;
+
+impl<A: AsRef<str>, U: AsRef<str>> Client<A, U> {
+    /// Creates a new [`ChartTopArtistsFetcher`] that can be used to fetch the most listened artists
+    /// on Last.fm (`chart.getTopArtists`).
+    pub async fn chart_top_artists(&self) -> Result<ChartTopArtistsFetcher, Error> {
+        PageFetcher::new(
+            self.transport(),
+            PageRequest {
+                method: "chart.getTopArtists",
+                params: Params::new().with("limit", 200),
+                root: "artists",
+                item: "artist",
+            },
+        )
+        .await
+    }
+
+    /// Creates a new [`ChartTopTracksFetcher`] that can be used to fetch the most listened tracks
+    /// on Last.fm (`chart.getTopTracks`).
+    pub async fn chart_top_tracks(&self) -> Result<ChartTopTracksFetcher, Error> {
+        PageFetcher::new(
+            self.transport(),
+            PageRequest {
+                method: "chart.getTopTracks",
+                params: Params::new().with("limit", 200),
+                root: "tracks",
+                item: "track",
+            },
+        )
+        .await
+    }
+
+    /// Creates a new [`ChartTopTagsFetcher`] that can be used to fetch the most used tags
+    /// on Last.fm (`chart.getTopTags`).
+    pub async fn chart_top_tags(&self) -> Result<ChartTopTagsFetcher, Error> {
+        PageFetcher::new(
+            self.transport(),
+            PageRequest {
+                method: "chart.getTopTags",
+                params: Params::new().with("limit", 200),
+                root: "tags",
+                item: "tag",
+            },
+        )
+        .await
+    }
+
+    /// Creates a new [`ChartTopArtistsFetcher`] that can be used to fetch the most listened artists
+    /// in the given country (`geo.getTopArtists`).
+    pub async fn geo_top_artists(&self, country: Country) -> Result<ChartTopArtistsFetcher, Error> {
+        PageFetcher::new(
+            self.transport(),
+            PageRequest {
+                method: "geo.getTopArtists",
+                params: Params::new().with("country", country).with("limit", 200),
+                root: "topartists",
+                item: "artist",
+            },
+        )
+        .await
+    }
+
+    /// Creates a new [`ChartTopTracksFetcher`] that can be used to fetch the most listened tracks
+    /// in the given country (`geo.getTopTracks`).
+    pub async fn geo_top_tracks(&self, country: Country) -> Result<ChartTopTracksFetcher, Error> {
+        PageFetcher::new(
+            self.transport(),
+            PageRequest {
+                method: "geo.getTopTracks",
+                params: Params::new().with("country", country).with("limit", 200),
+                root: "tracks",
+                item: "track",
+            },
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serde_helpers::items_under;
+    use serde_json::json;
+
+    #[test]
+    fn it_deserializes_chart_top_artists() {
+        let raw = json!({
+          "artists": {
+            "artist": [
+              {
+                "name": "The Weeknd",
+                "playcount": "406215978",
+                "listeners": "4325113",
+                "mbid": "c8b03190-306c-4120-bb0b-6f2ebfc06ea9",
+                "url": "https://www.last.fm/music/The+Weeknd",
+                "streamable": "0",
+                "image": []
+              }
+            ],
+            "@attr": { "page": "1", "perPage": "50", "totalPages": "200", "total": "10000" }
+          }
+        });
+
+        let artists: Vec<TopArtist> = items_under(&raw, "artists", "artist").unwrap();
+        insta::assert_debug_snapshot!(artists);
+    }
+
+    #[test]
+    fn it_deserializes_chart_top_tags() {
+        let raw = json!({
+          "tags": {
+            "tag": {
+              "name": "rock",
+              "url": "https://www.last.fm/tag/rock",
+              "reach": "404062",
+              "taggings": "4054497",
+              "streamable": "1",
+              "wiki": {}
+            },
+            "@attr": { "page": "1", "perPage": "50", "totalPages": "200", "total": "10000" }
+          }
+        });
+
+        let tags: Vec<TagInfo> = items_under(&raw, "tags", "tag").unwrap();
+        assert_eq!(tags[0].name, "rock");
+        assert_eq!(tags[0].reach, 404062);
+        assert_eq!(tags[0].taggings, 4054497);
+    }
+}
@@ -6,7 +6,8 @@
 //! It also provides a [`ClientBuilder`] to create a new [`Client`].
 use crate::{
     errors::Error,
-    recent_tracks_page::{RecentTracksPage, RecentTracksResponse},
+    recent_tracks_page::RecentTracksPage,
+    request::{Auth, Params, Transport},
     retry_strategy::{JitteredBackoff, RetryStrategy},
     track::{NowPlayingTrack, RecordedTrack, Track},
 };
@@ -50,10 +51,17 @@ fn mask_api_key(api_key: &str) -> String {
 }
 
 /// A client for the Last.fm API.
+///
+/// Read-only methods only need an `api_key`. Methods that write data or access private data
+/// also need the `api_secret` (used to sign requests) and a `session_key` for the authenticated user.
 #[derive(TypedBuilder, Clone)]
 pub struct Client<A: AsRef<str>, U: AsRef<str>> {
     api_key: A,
     username: U,
+    #[builder(default, setter(strip_option, into))]
+    api_secret: Option<String>,
+    #[builder(default, setter(strip_option, into))]
+    session_key: Option<String>,
     #[builder(default = DEFAULT_CLIENT.clone())]
     reqwest_client: reqwest::Client,
     #[builder(default = DEFAULT_BASE_URL.parse().unwrap())]
@@ -67,25 +75,38 @@ impl<A: AsRef<str>, U: AsRef<str>> Debug for Client<A, U> {
         f.debug_struct("Client")
             .field("api_key", &mask_api_key(self.api_key.as_ref()).as_str())
             .field("username", &self.username.as_ref())
+            .field("api_secret", &self.api_secret.as_deref().map(mask_api_key))
+            .field(
+                "session_key",
+                &self.session_key.as_deref().map(mask_api_key),
+            )
             .field("reqwest_client", &self.reqwest_client)
             .field("base_url", &self.base_url)
             .finish()
     }
 }
 
+/// How much detail is requested for each recent track.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TrackDetail {
+    /// Artists carry their URL and images, and tracks carry the `loved` flag.
+    Extended,
+    /// Pages are about half the size, but artists only carry their name and MusicBrainz ID
+    /// and the `loved` flag of the tracks is always `false`.
+    Compact,
+}
+
 /// Structs that can be used to get a stream of [`RecordedTrack`]s.
 #[non_exhaustive]
 pub struct RecentTracksFetcher {
-    api_key: String,
+    transport: Transport,
     username: String,
     current_page: VecDeque<RecordedTrack>,
     from: Option<i64>,
     to: Option<i64>,
+    detail: TrackDetail,
     /// The total number of tracks available in the stream.
     pub total_tracks: u64,
-    reqwest_client: reqwest::Client,
-    base_url: Url,
-    retry_strategy: Arc<dyn RetryStrategy>,
 }
 
 impl RecentTracksFetcher {
@@ -114,14 +135,12 @@ impl RecentTracksFetcher {
                     }
                     None => {
                         let next_page = get_page(GetPageOptions {
-                            client: &self.reqwest_client,
-                            retry_strategy: &*self.retry_strategy,
-                            base_url: &self.base_url,
-                            api_key: &self.api_key,
+                            transport: &self.transport,
                             username: &self.username,
                             limit: 200,
                             from: self.from,
-                            to: self.to
+                            to: self.to,
+                            detail: self.detail,
                         }).await?;
                         if next_page.tracks.is_empty() {
                             break;
@@ -136,69 +155,56 @@ impl RecentTracksFetcher {
     }
 }
 
-/// Configuration options used for the [`Client::get_page`] function.
+/// Configuration options used for the [`get_page`] function.
 struct GetPageOptions<'a> {
-    client: &'a reqwest::Client,
-    retry_strategy: &'a dyn RetryStrategy,
-    base_url: &'a Url,
-    api_key: &'a str,
+    transport: &'a Transport,
     username: &'a str,
     limit: u32,
     from: Option<i64>,
     to: Option<i64>,
+    detail: TrackDetail,
+}
+
+/// Builds the parameters of a `user.getrecenttracks` request.
+fn page_params(options: &GetPageOptions<'_>) -> Params {
+    Params::new()
+        .with("user", options.username)
+        .with(
+            "extended",
+            u8::from(options.detail == TrackDetail::Extended),
+        )
+        .with("limit", options.limit)
+        .with_opt("from", options.from)
+        .with_opt("to", options.to)
 }
 
 /// Gets a page of tracks from the Last.fm API.
 async fn get_page(options: GetPageOptions<'_>) -> Result<RecentTracksPage, Error> {
-    let mut url_query = vec![
-        ("method", "user.getrecenttracks".to_string()),
-        ("user", options.username.to_string()),
-        ("format", "json".to_string()),
-        ("extended", "1".to_string()),
-        ("limit", options.limit.to_string()),
-        ("api_key", options.api_key.to_string()),
-    ];
-
-    if let Some(from) = options.from {
-        url_query.push(("from", from.to_string()));
-    }
+    options
+        .transport
+        .get("user.getrecenttracks", page_params(&options), Auth::None)
+        .await
+}
 
-    if let Some(to) = options.to {
-        url_query.push(("to", to.to_string()));
-    }
+/// Fetches the currently playing track of the given user (if any).
+pub(crate) async fn get_now_playing(
+    transport: &Transport,
+    username: &str,
+) -> Result<Option<NowPlayingTrack>, Error> {
+    let page = get_page(GetPageOptions {
+        transport,
+        username,
+        limit: 1,
+        from: None,
+        to: None,
+        detail: TrackDetail::Extended,
+    })
+    .await?;
 
-    let url = Url::parse_with_params(options.base_url.as_str(), &url_query).unwrap();
-
-    let mut errors: Vec<Error> = Vec::new();
-    let mut num_retries: usize = 0;
-    while let Some(retry_delay) = options.retry_strategy.should_retry_after(num_retries) {
-        let res = options.client.get(&(url).to_string()).send().await;
-        match res {
-            Ok(res) => {
-                let page: RecentTracksResponse = res.json().await?;
-                match page {
-                    RecentTracksResponse::RecentTracksPage(page) => {
-                        return Ok(page);
-                    }
-                    RecentTracksResponse::Error(e) => {
-                        tracing::error!("LastFm Error: {}", e.message);
-                        if !e.is_retriable() {
-                            return Err(e.into());
-                        }
-                        tokio::time::sleep(retry_delay).await;
-                    }
-                }
-            }
-            Err(e) => {
-                tracing::error!("Error: {}", e);
-                errors.push(e.into());
-                tokio::time::sleep(retry_delay).await;
-            }
-        }
-        num_retries += 1;
+    match page.tracks.into_iter().next() {
+        Some(Track::NowPlaying(t)) => Ok(Some(t)),
+        _ => Ok(None),
     }
-
-    Err(Error::TooManyRetry(errors))
 }
 
 impl<A: AsRef<str>, U: AsRef<str>> Client<A, U> {
@@ -215,49 +221,122 @@ impl<A: AsRef<str>, U: AsRef<str>> Client<A, U> {
     /// Creates a new [`Client`] with the given username.
     /// The API key is read from the `LASTFM_API_KEY` environment variable.
     /// If the environment variable is not set, this function will return an error.
+    ///
+    /// The optional `LASTFM_API_SECRET` and `LASTFM_SESSION_KEY` environment variables are also
+    /// read (if set and not empty) to configure the client for authenticated calls.
     pub fn try_from_env(username: U) -> Result<Client<String, U>, VarError> {
         let api_key = env::var("LASTFM_API_KEY")?;
-        Ok(Client::builder()
+        let mut client = Client::builder()
             .username(username)
             .api_key(api_key)
-            .build())
+            .build();
+        client.api_secret = env::var("LASTFM_API_SECRET").ok().filter(|v| !v.is_empty());
+        client.session_key = env::var("LASTFM_SESSION_KEY")
+            .ok()
+            .filter(|v| !v.is_empty());
+        Ok(client)
+    }
+
+    /// The session key used for authenticated calls (if any).
+    pub fn session_key(&self) -> Option<&str> {
+        self.session_key.as_deref()
+    }
+
+    /// Sets the session key used for authenticated calls
+    /// (e.g. the [`Session::key`](crate::auth::Session) obtained through [`Client::get_session`]).
+    pub fn set_session_key(&mut self, session_key: impl Into<String>) {
+        self.session_key = Some(session_key.into());
     }
 
     /// Fetches the currently playing track for the user (if any)
     pub async fn now_playing(&self) -> Result<Option<NowPlayingTrack>, Error> {
-        let page = self.get_page_helper(1, None, None).await?;
+        get_now_playing(&self.transport(), self.username.as_ref()).await
+    }
 
-        match page.tracks.first() {
-            Some(Track::NowPlaying(t)) => Ok(Some(t.clone())),
-            _ => Ok(None),
+    /// Returns a client that fetches data for the given user, sharing the configuration
+    /// (HTTP client, base URL, retry strategy and credentials) of the current client.
+    ///
+    /// This allows a single client to be shared (e.g. in a multi-tenant service) and the user
+    /// to be selected per call:
+    ///
+    /// ```rust,no_run
+    /// # use lastfm::Client;
+    /// #
+    /// # async fn example() -> Result<(), lastfm::errors::Error> {
+    /// let client = Client::builder().api_key("YOUR_API_KEY").username("YOUR_USERNAME").build();
+    /// let alice_tracks = client.user("alice").recent_tracks(None, None).await?;
+    /// let bob_now_playing = client.user("bob").now_playing().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Note that authenticated calls (e.g. [`Client::scrobble`]) are still performed on behalf of the
+    /// user owning the session key.
+    pub fn user<V: AsRef<str>>(&self, username: V) -> Client<A, V>
+    where
+        A: Clone,
+    {
+        Client {
+            api_key: self.api_key.clone(),
+            username,
+            api_secret: self.api_secret.clone(),
+            session_key: self.session_key.clone(),
+            reqwest_client: self.reqwest_client.clone(),
+            base_url: self.base_url.clone(),
+            retry_strategy: self.retry_strategy.clone(),
         }
     }
 
     /// Creates a new [`RecentTracksFetcher`] that can be used to fetch all of the user's recent tracks.
-    pub async fn all_tracks(self) -> Result<RecentTracksFetcher, Error> {
-        self.recent_tracks(None, None).await
+    ///
+    /// Tracks are fetched in compact form (see [`Client::recent_tracks_compact`]) to keep a full
+    /// history export as light as possible.
+    pub async fn all_tracks(&self) -> Result<RecentTracksFetcher, Error> {
+        self.recent_tracks_compact(None, None).await
     }
 
     /// Creates a new [`RecentTracksFetcher`] that can be used to fetch the user's recent tracks in a given time range.
     ///
     /// The `from` and `to` parameters are Unix timestamps (in seconds).
     pub async fn recent_tracks(
-        self,
+        &self,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<RecentTracksFetcher, Error> {
+        self.recent_tracks_with_detail(from, to, TrackDetail::Extended)
+            .await
+    }
+
+    /// Same as [`Client::recent_tracks`], but tracks are fetched in compact form.
+    ///
+    /// Pages are about half the size, but artists only carry their name and MusicBrainz ID
+    /// and the `loved` flag of the tracks is always `false`.
+    pub async fn recent_tracks_compact(
+        &self,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<RecentTracksFetcher, Error> {
+        self.recent_tracks_with_detail(from, to, TrackDetail::Compact)
+            .await
+    }
+
+    /// Creates a new [`RecentTracksFetcher`] that fetches tracks with the given level of detail.
+    async fn recent_tracks_with_detail(
+        &self,
         from: Option<i64>,
         to: Option<i64>,
+        detail: TrackDetail,
     ) -> Result<RecentTracksFetcher, Error> {
-        let page = self.get_page_helper(200, from, to).await?;
+        let page = self.get_page_helper(200, from, to, detail).await?;
 
         let mut fetcher = RecentTracksFetcher {
-            api_key: self.api_key.as_ref().to_string(),
+            transport: self.transport(),
             username: self.username.as_ref().to_string(),
             current_page: VecDeque::new(),
             from,
             to,
+            detail,
             total_tracks: page.total_tracks,
-            reqwest_client: self.reqwest_client,
-            base_url: self.base_url,
-            retry_strategy: self.retry_strategy,
         };
 
         fetcher.update_current_page(page);
@@ -275,17 +354,83 @@ impl<A: AsRef<str>, U: AsRef<str>> Client<A, U> {
         limit: u32,
         from: Option<i64>,
         to: Option<i64>,
+        detail: TrackDetail,
     ) -> Result<RecentTracksPage, Error> {
         get_page(GetPageOptions {
-            client: &self.reqwest_client,
-            retry_strategy: &*self.retry_strategy,
-            base_url: &self.base_url,
-            api_key: self.api_key.as_ref(),
+            transport: &self.transport(),
             username: self.username.as_ref(),
-            limit: limit,
+            limit,
             from,
             to,
+            detail,
         })
         .await
     }
+
+    /// The username used by this client.
+    pub(crate) fn username(&self) -> &str {
+        self.username.as_ref()
+    }
+
+    /// The API key used by this client.
+    pub(crate) fn api_key(&self) -> &str {
+        self.api_key.as_ref()
+    }
+
+    /// Builds the [`Transport`] used to send requests on behalf of this client.
+    pub(crate) fn transport(&self) -> Transport {
+        Transport {
+            reqwest_client: self.reqwest_client.clone(),
+            base_url: self.base_url.clone(),
+            retry_strategy: self.retry_strategy.clone(),
+            api_key: self.api_key.as_ref().to_string(),
+            api_secret: self.api_secret.clone(),
+            session_key: self.session_key.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_selects_another_user_without_consuming_the_client() {
+        let client = Client::builder()
+            .api_key("some-api-key")
+            .username("loige")
+            .api_secret("some-secret")
+            .build();
+
+        let alice = client.user("alice".to_string());
+        assert_eq!(alice.username(), "alice");
+        assert_eq!(alice.api_key(), "some-api-key");
+        assert_eq!(alice.transport().api_secret.as_deref(), Some("some-secret"));
+        assert_eq!(client.username(), "loige");
+    }
+
+    #[test]
+    fn it_sends_the_extended_flag_for_the_requested_detail() {
+        let transport = Client::builder()
+            .api_key("some-api-key")
+            .username("loige")
+            .build()
+            .transport();
+        let options = |detail| GetPageOptions {
+            transport: &transport,
+            username: "loige",
+            limit: 200,
+            from: None,
+            to: Some(1676284092),
+            detail,
+        };
+
+        let params = page_params(&options(TrackDetail::Compact)).0;
+        assert!(params.contains(&("extended".to_string(), "0".to_string())));
+        assert!(params.contains(&("to".to_string(), "1676284092".to_string())));
+        assert!(!params.iter().any(|(k, _)| k == "from"));
+
+        let params = page_params(&options(TrackDetail::Extended)).0;
+        assert!(params.contains(&("extended".to_string(), "1".to_string())));
+    }
 }
@@ -1,7 +1,7 @@
 //! # Errors
 //!
 //! Errors that can occur when interacting with the LastFM Client.
-use crate::error_response::ErrorResponse;
+use crate::{error_response::ErrorResponse, scrobble::ScrobbleResult};
 use thiserror::Error;
 
 /// Errors that can occur when interacting with the LastFM Client.
@@ -11,8 +11,87 @@ pub enum Error {
     Http(#[from] reqwest::Error),
     #[error("JSON deserialization error: {0}")]
     Deserialization(#[from] serde_json::Error),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
     #[error("Unretryable error from LastFM: {0}")]
-    UnretriableLastFm(#[from] ErrorResponse),
+    UnretriableLastFm(ErrorResponse),
+    #[error("Authentication failed: {0}")]
+    AuthenticationFailed(ErrorResponse),
+    #[error("Invalid session key: {0}")]
+    InvalidSessionKey(ErrorResponse),
+    #[error("Unauthorized token: {0}")]
+    UnauthorizedToken(ErrorResponse),
+    #[error("Token expired: {0}")]
+    TokenExpired(ErrorResponse),
     #[error("Too many retries")]
     TooManyRetry(Vec<Error>),
+    #[error("Missing API secret: it is required to sign requests")]
+    MissingApiSecret,
+    #[error("Missing session key: the request needs an authenticated user")]
+    MissingSessionKey,
+    #[error("Invalid country: {0} is not an ISO 3166-1 country")]
+    InvalidCountry(String),
+    #[error("Too many tags: {0} were given, but at most 10 can be added per request")]
+    TooManyTags(usize),
+    #[error("Invalid tag: {0:?} is blank or contains a comma")]
+    InvalidTag(String),
+    #[error("Scrobbling failed after {sent} scrobbles were recorded: {source}")]
+    PartialScrobble {
+        /// The merged result of the batches recorded by Last.fm.
+        result: ScrobbleResult,
+        /// The number of scrobbles recorded by Last.fm, i.e. the index of the first scrobble that was not sent.
+        sent: usize,
+        /// The error of the failed batch.
+        source: Box<Error>,
+    },
+    #[error("Incomplete scrobble response: {sent} scrobbles were sent, but only {received} results were returned")]
+    IncompleteScrobbleResponse { sent: usize, received: usize },
+}
+
+/// Maps the authentication related error codes to their own variant.
+///
+/// See <https://www.last.fm/api/errorcodes> for more details.
+impl From<ErrorResponse> for Error {
+    fn from(e: ErrorResponse) -> Self {
+        match e.error {
+            4 => Error::AuthenticationFailed(e),
+            9 => Error::InvalidSessionKey(e),
+            14 => Error::UnauthorizedToken(e),
+            15 => Error::TokenExpired(e),
+            _ => Error::UnretriableLastFm(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_response(error: u32) -> ErrorResponse {
+        ErrorResponse {
+            error,
+            message: "Some message".to_string(),
+        }
+    }
+
+    #[test]
+    fn it_maps_auth_error_codes() {
+        assert!(matches!(
+            error_response(4).into(),
+            Error::AuthenticationFailed(_)
+        ));
+        assert!(matches!(
+            error_response(9).into(),
+            Error::InvalidSessionKey(_)
+        ));
+        assert!(matches!(
+            error_response(14).into(),
+            Error::UnauthorizedToken(_)
+        ));
+        assert!(matches!(error_response(15).into(), Error::TokenExpired(_)));
+        assert!(matches!(
+            error_response(6).into(),
+            Error::UnretriableLastFm(_)
+        ));
+    }
 }
@@ -5,7 +5,7 @@ use serde::{de::Error, Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 
 /// A set of images for a Last.fm entity.
-#[derive(Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct ImageSet {
     pub small: Option<String>,
     pub medium: Option<String>,
@@ -20,12 +20,7 @@ impl<'de> Deserialize<'de> for ImageSet {
     {
         let raw_images: Vec<HashMap<String, String>> = Deserialize::deserialize(deserializer)?;
 
-        let mut image_set = ImageSet {
-            small: None,
-            medium: None,
-            large: None,
-            extralarge: None,
-        };
+        let mut image_set = ImageSet::default();
 
         for image in raw_images {
             let size = image
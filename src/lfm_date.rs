@@ -3,10 +3,15 @@
 //! Defines the [`LfmDate`] struct and its methods.
 use chrono::{DateTime, LocalResult, TimeZone, Utc};
 use serde::{de::Error, Deserialize, Deserializer, Serialize};
+use serde_json::Value;
 use std::{collections::HashMap, ops::Deref};
 
 /// A Last.fm date.
-#[derive(Serialize, Debug, Clone)]
+///
+/// Last.fm encodes dates as objects with a Unix timestamp, either in a `uts` field
+/// (e.g. `{ "uts": "1676284092", "#text": "13 Feb 2023, 10:28" }`) or in a `unixtime` field
+/// (e.g. `{ "unixtime": "1037793040", "#text": 1037793040 }`).
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct LfmDate(DateTime<Utc>);
 
 impl<'de> Deserialize<'de> for LfmDate {
@@ -14,13 +19,18 @@ impl<'de> Deserialize<'de> for LfmDate {
     where
         D: Deserializer<'de>,
     {
-        let raw_data: HashMap<String, String> = Deserialize::deserialize(deserializer)?;
+        let raw_data: HashMap<String, Value> = Deserialize::deserialize(deserializer)?;
 
-        let uts = raw_data
+        let raw_uts = raw_data
             .get("uts")
-            .ok_or_else(|| D::Error::missing_field("uts"))?
-            .parse::<i64>()
-            .map_err(|_| D::Error::custom("Failed to parse uts as i64"))?;
+            .or_else(|| raw_data.get("unixtime"))
+            .ok_or_else(|| D::Error::missing_field("uts"))?;
+        let uts = match raw_uts {
+            Value::String(s) => s.parse::<i64>().ok(),
+            Value::Number(n) => n.as_i64(),
+            _ => None,
+        }
+        .ok_or_else(|| D::Error::custom("Failed to parse uts as i64"))?;
 
         let local_result = Utc.timestamp_opt(uts, 0);
         if let LocalResult::Single(date_time) = local_result {
@@ -55,4 +65,16 @@ mod tests {
         let expected = "2023-02-13 10:28:12 UTC";
         assert_eq!(lfm_date.to_string(), expected);
     }
+
+    #[test]
+    fn it_deserializes_unixtime() {
+        let json_value = json!({
+          "unixtime": "1037793040",
+          "#text": 1037793040
+        });
+
+        let lfm_date: LfmDate = serde_json::from_value(json_value).unwrap();
+        let expected = "2002-11-20 11:50:40 UTC";
+        assert_eq!(lfm_date.to_string(), expected);
+    }
 }
@@ -35,6 +35,7 @@
 //! ```
 //!
 //! Note: this method will panic if `LASTFM_API_KEY` is not set.
+//! If `LASTFM_API_SECRET` and `LASTFM_SESSION_KEY` are set, they are used to configure the client for authenticated calls.
 //!
 //! Alternatively, you can use `try_from_env` which will return a `Result`.
 //!
@@ -60,6 +61,30 @@
 //! let client = Client::builder().api_key("YOUR_API_KEY").username("YOUR_USERNAME").build();
 //! ```
 //!
+//! ### Authenticated calls
+//!
+//! Methods that write data (e.g. scrobbling) need your API secret and a session key.
+//! If you don't have a session key yet, you can log in with your username and password:
+//!
+//! ```rust,no_run
+//! # use lastfm::Client;
+//! #
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!   let mut client = Client::builder()
+//!     .api_key("YOUR_API_KEY")
+//!     .username("YOUR_USERNAME")
+//!     .api_secret("YOUR_API_SECRET")
+//!     .build();
+//!   let session = client.login("YOUR_USERNAME", "YOUR_PASSWORD").await?;
+//!   println!("Logged in as {}", session.name);
+//!
+//!   Ok(())
+//! }
+//! ```
+//!
+//! Check out the [`auth`](https://docs.rs/lastfm/latest/lastfm/auth/index.html) module for the web/desktop authentication flow.
+//!
 //! ### Fetch the track you are currently playing
 //!
 //! ```rust,no_run
@@ -117,14 +142,38 @@
 #[macro_use]
 extern crate lazy_static;
 
+pub mod album;
+pub mod album_info;
 pub mod artist;
+pub mod artist_info;
+pub mod auth;
+pub mod charts;
 pub mod client;
+pub mod country;
 pub mod error_response;
 pub mod errors;
 pub mod imageset;
 pub mod lfm_date;
+pub mod library;
+pub mod loved_tracks;
+pub mod pagination;
 pub mod recent_tracks_page;
+pub mod request;
 pub mod retry_strategy;
+pub mod scrobble;
+pub mod scrobble_queue;
+pub mod search;
+mod serde_helpers;
+pub mod tag;
+pub mod tag_info;
+pub mod tagging;
+pub mod top;
 pub mod track;
+pub mod track_info;
+pub mod track_scrobbles;
+pub mod user;
+pub mod user_tags;
+pub mod weekly_charts;
+pub mod wiki;
 pub use client::{Client, ClientBuilder};
 pub use reqwest;
@@ -0,0 +1,108 @@
+//! # Library
+//!
+//! Defines the [`LibraryArtist`] struct and the method to list all the artists in the user's library.
+//!
+//! ```rust,no_run
+//! use futures_util::pin_mut;
+//! use futures_util::stream::StreamExt;
+//! # use lastfm::Client;
+//! #
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!   let client = Client::builder().api_key("YOUR_API_KEY").username("YOUR_USERNAME").build();
+//!   let artists = client.library_artists().await?;
+//!   println!("Total artists: {}", artists.total);
+//!
+//!   let artists = artists.into_stream();
+//!   pin_mut!(artists);
+//!   while let Some(artist) = artists.next().await {
+//!     let artist = artist?;
+//!     println!("{} ({} plays)", artist.artist.name, artist.playcount);
+//!   }
+//!
+//!   Ok(())
+//! }
+//! ```
+use crate::{
+    artist::Artist,
+    errors::Error,
+    pagination::{PageFetcher, PageRequest},
+    request::Params,
+    serde_helpers::number_from_any,
+    Client,
+};
+use serde::{Deserialize, Serialize};
+
+/// An artist in the user's library.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LibraryArtist {
+    #[serde(flatten)]
+    pub artist: Artist,
+    /// How many times the user listened to the artist.
+    #[serde(deserialize_with = "number_from_any")]
+    pub playcount: u64,
+    /// How many tags the user applied to the artist.
+    #[serde(default, deserialize_with = "number_from_any")]
+    pub tagcount: u64,
+}
+
+/// Structs that can be used to get a stream of [`LibraryArtist`]s.
+pub type LibraryArtistsFetcher = PageFetcher<LibraryArtist>;
+
+impl<A: AsRef<str>, U: AsRef<str>> Client<A, U> {
+    /// Creates a new [`LibraryArtistsFetcher`] that can be used to fetch all the artists in the user's
+    /// library, most listened first (`library.getArtists`).
+    pub async fn library_artists(&self) -> Result<LibraryArtistsFetcher, Error> {
+        PageFetcher::new(
+            self.transport(),
+            PageRequest {
+                method: "library.getArtists",
+                params: Params::new()
+                    .with("user", self.username())
+                    .with("limit", 200),
+                root: "artists",
+                item: "artist",
+            },
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serde_helpers::items_under;
+    use serde_json::json;
+
+    #[test]
+    fn it_deserializes_library_artists() {
+        let raw = json!({
+          "artists": {
+            "artist": [
+              {
+                "name": "Comete",
+                "playcount": "1127",
+                "tagcount": "2",
+                "mbid": "",
+                "url": "https://www.last.fm/music/Comete",
+                "streamable": "0",
+                "image": []
+              },
+              {
+                "name": "Editors",
+                "playcount": "845",
+                "tagcount": "0",
+                "mbid": "6f1a58bf-9417-4bf1-8b6d-0d4e8a5e9c20",
+                "url": "https://www.last.fm/music/Editors",
+                "streamable": "0",
+                "image": []
+              }
+            ],
+            "@attr": { "user": "loige", "page": "1", "perPage": "50", "totalPages": "1", "total": "2" }
+          }
+        });
+
+        let artists: Vec<LibraryArtist> = items_under(&raw, "artists", "artist").unwrap();
+        insta::assert_debug_snapshot!(artists);
+    }
+}
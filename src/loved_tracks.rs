@@ -0,0 +1,88 @@
+//! # Loved tracks
+//!
+//! Defines the [`LovedTrack`] struct and the methods to love, unlove and list the loved tracks of a user.
+use crate::{
+    artist::Artist,
+    errors::Error,
+    imageset::ImageSet,
+    pagination::{PageFetcher, PageRequest},
+    request::{Auth, Params},
+    serde_helpers::{datetime_from_lfm_date, empty_string_as_none},
+    Client,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A track loved by a Last.fm user.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LovedTrack {
+    pub artist: Artist,
+    pub name: String,
+    #[serde(default)]
+    pub image: ImageSet,
+    pub url: String,
+    /// The MusicBrainz ID of the track (if known).
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub mbid: Option<String>,
+    /// When the track was loved.
+    #[serde(deserialize_with = "datetime_from_lfm_date")]
+    pub date: DateTime<Utc>,
+}
+
+/// Structs that can be used to get a stream of [`LovedTrack`]s.
+pub type LovedTracksFetcher = PageFetcher<LovedTrack>;
+
+impl<A: AsRef<str>, U: AsRef<str>> Client<A, U> {
+    /// Loves a track for the authenticated user (`track.love`).
+    ///
+    /// Requires the client to be configured with an `api_secret` and a `session_key`.
+    pub async fn love(&self, artist: &str, track: &str) -> Result<(), Error> {
+        let params = Params::new().with("artist", artist).with("track", track);
+        self.transport()
+            .post::<Value>("track.love", params, Auth::Session)
+            .await?;
+        Ok(())
+    }
+
+    /// Unloves a track for the authenticated user (`track.unlove`).
+    ///
+    /// Requires the client to be configured with an `api_secret` and a `session_key`.
+    pub async fn unlove(&self, artist: &str, track: &str) -> Result<(), Error> {
+        let params = Params::new().with("artist", artist).with("track", track);
+        self.transport()
+            .post::<Value>("track.unlove", params, Auth::Session)
+            .await?;
+        Ok(())
+    }
+
+    /// Creates a new [`LovedTracksFetcher`] that can be used to fetch all of the user's loved tracks
+    /// (most recently loved first).
+    pub async fn loved_tracks(&self) -> Result<LovedTracksFetcher, Error> {
+        PageFetcher::new(
+            self.transport(),
+            PageRequest {
+                method: "user.getLovedTracks",
+                params: Params::new()
+                    .with("user", self.username())
+                    .with("limit", 200),
+                root: "lovedtracks",
+                item: "track",
+            },
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_deserializes_a_loved_track() {
+        let json_data = include_str!("fixtures/loved_track.json");
+
+        let track: LovedTrack = serde_json::from_str(json_data).unwrap();
+        insta::assert_debug_snapshot!(track);
+    }
+}
@@ -0,0 +1,268 @@
+//! # Pagination
+//!
+//! Defines the [`PageFetcher`] struct, used to stream the items of the paginated Last.fm endpoints
+//! (e.g. `user.getLovedTracks`).
+use crate::{
+    errors::Error,
+    request::{Auth, Params, Transport},
+    serde_helpers::{items_under, number_from_any},
+};
+use async_stream::try_stream;
+use serde::{de::DeserializeOwned, de::Error as _, Deserialize};
+use serde_json::Value;
+use std::collections::VecDeque;
+use tokio_stream::Stream;
+
+/// Describes how to request and parse the pages of a paginated endpoint.
+#[derive(Debug, Clone)]
+pub(crate) struct PageRequest {
+    /// The Last.fm method (e.g. `user.getLovedTracks`).
+    pub(crate) method: &'static str,
+    /// The method specific parameters (`page` is added automatically).
+    pub(crate) params: Params,
+    /// The key of the object wrapping the page in the response (e.g. `lovedtracks`).
+    pub(crate) root: &'static str,
+    /// The key of the list of items inside the root object (e.g. `track`),
+    /// or a dot separated path for items nested more deeply (e.g. `artists.artist`).
+    pub(crate) item: &'static str,
+}
+
+/// The pagination attributes returned with every page.
+#[derive(Deserialize)]
+struct PageAttr {
+    #[serde(deserialize_with = "number_from_any")]
+    page: u32,
+    #[serde(rename = "totalPages", deserialize_with = "number_from_any")]
+    total_pages: u32,
+    #[serde(deserialize_with = "number_from_any")]
+    total: u64,
+}
+
+/// The pagination attributes returned with every page of the search endpoints (e.g. `artist.search`).
+#[derive(Deserialize)]
+struct OpenSearchAttr {
+    #[serde(rename = "opensearch:Query")]
+    query: OpenSearchQuery,
+    #[serde(
+        rename = "opensearch:totalResults",
+        deserialize_with = "number_from_any"
+    )]
+    total_results: u64,
+    #[serde(
+        rename = "opensearch:itemsPerPage",
+        deserialize_with = "number_from_any"
+    )]
+    items_per_page: u64,
+}
+
+#[derive(Deserialize)]
+struct OpenSearchQuery {
+    #[serde(rename = "startPage", deserialize_with = "number_from_any")]
+    start_page: u32,
+}
+
+impl From<OpenSearchAttr> for PageAttr {
+    fn from(attr: OpenSearchAttr) -> Self {
+        let total_pages = match attr.items_per_page {
+            0 => 0,
+            per_page => attr.total_results.div_ceil(per_page),
+        };
+        PageAttr {
+            page: attr.query.start_page,
+            total_pages: u32::try_from(total_pages).unwrap_or(u32::MAX),
+            total: attr.total_results,
+        }
+    }
+}
+
+/// A single page of items.
+struct Page<T> {
+    items: Vec<T>,
+    page: u32,
+    total_pages: u32,
+    total: u64,
+}
+
+impl<T: DeserializeOwned> Page<T> {
+    /// Parses a raw page, where items and pagination attributes are wrapped in the `root` object.
+    ///
+    /// Search endpoints use `opensearch:*` fields (instead of `@attr`) for the pagination attributes.
+    ///
+    /// Some endpoints (e.g. `user.getTopTags`) return all their items at once, without pagination
+    /// attributes: in that case the response is treated as a single page.
+    fn parse(raw: Value, root: &str, item: &str) -> Result<Self, serde_json::Error> {
+        let raw_root = raw
+            .get(root)
+            .ok_or_else(|| serde_json::Error::custom(format!("missing field `{root}`")))?;
+
+        let attr: Option<PageAttr> = match raw_root.get("@attr") {
+            _ if raw_root.get("opensearch:totalResults").is_some() => {
+                Some(serde_json::from_value::<OpenSearchAttr>(raw_root.clone())?.into())
+            }
+            Some(raw_attr) if raw_attr.get("totalPages").is_some() => {
+                Some(serde_json::from_value(raw_attr.clone())?)
+            }
+            _ => None,
+        };
+
+        let items = items_under(&raw, root, item)?;
+
+        Ok(match attr {
+            Some(attr) => Page {
+                items,
+                page: attr.page,
+                total_pages: attr.total_pages,
+                total: attr.total,
+            },
+            None => Page {
+                total: items.len() as u64,
+                items,
+                page: 1,
+                total_pages: 1,
+            },
+        })
+    }
+}
+
+/// Fetches a single page of the given request.
+async fn fetch_page<T: DeserializeOwned>(
+    transport: &Transport,
+    request: &PageRequest,
+    page: u32,
+) -> Result<Page<T>, Error> {
+    let params = request.params.clone().with("page", page);
+    let raw: Value = transport.get(request.method, params, Auth::None).await?;
+
+    Ok(Page::parse(raw, request.root, request.item)?)
+}
+
+/// Structs that can be used to get a stream of the items of a paginated endpoint.
+#[non_exhaustive]
+pub struct PageFetcher<T> {
+    transport: Transport,
+    request: PageRequest,
+    current_page: VecDeque<T>,
+    next_page: u32,
+    total_pages: u32,
+    /// The total number of items available in the stream.
+    pub total: u64,
+}
+
+impl<T: DeserializeOwned> PageFetcher<T> {
+    /// Creates a new fetcher, eagerly loading the first page.
+    pub(crate) async fn new(transport: Transport, request: PageRequest) -> Result<Self, Error> {
+        let page = fetch_page(&transport, &request, 1).await?;
+
+        let mut fetcher = PageFetcher {
+            transport,
+            request,
+            current_page: VecDeque::new(),
+            next_page: 1,
+            total_pages: 0,
+            total: page.total,
+        };
+        fetcher.update_current_page(page);
+
+        Ok(fetcher)
+    }
+
+    fn update_current_page(&mut self, page: Page<T>) {
+        self.current_page = page.items.into();
+        self.next_page = page.page + 1;
+        self.total_pages = page.total_pages;
+    }
+
+    /// Converts the current instance into a stream of items.
+    pub fn into_stream(mut self) -> impl Stream<Item = Result<T, Error>> {
+        try_stream! {
+            loop {
+                match self.current_page.pop_front() {
+                    Some(item) => {
+                        yield item;
+                    }
+                    None => {
+                        if self.next_page > self.total_pages {
+                            break;
+                        }
+                        let next_page = fetch_page(&self.transport, &self.request, self.next_page).await?;
+                        if next_page.items.is_empty() {
+                            break;
+                        }
+                        self.update_current_page(next_page);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn it_parses_a_page() {
+        let raw = json!({
+          "lovedtracks": {
+            "track": [{ "name": "Antenna" }, { "name": "Do You Feel" }],
+            "@attr": { "user": "loige", "totalPages": "3", "page": "2", "perPage": "2", "total": "6" }
+          }
+        });
+
+        let page: Page<Value> = Page::parse(raw, "lovedtracks", "track").unwrap();
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.page, 2);
+        assert_eq!(page.total_pages, 3);
+        assert_eq!(page.total, 6);
+    }
+
+    #[test]
+    fn it_parses_a_page_with_a_single_item() {
+        let raw = json!({
+          "lovedtracks": {
+            "track": { "name": "Antenna" },
+            "@attr": { "user": "loige", "totalPages": "1", "page": "1", "perPage": "50", "total": "1" }
+          }
+        });
+
+        let page: Page<Value> = Page::parse(raw, "lovedtracks", "track").unwrap();
+        assert_eq!(page.items, vec![json!({ "name": "Antenna" })]);
+    }
+
+    #[test]
+    fn it_parses_an_unpaginated_response_as_a_single_page() {
+        let raw = json!({
+          "toptags": {
+            "tag": [{ "name": "rock" }, { "name": "indie" }, { "name": "emo" }],
+            "@attr": { "user": "loige" }
+          }
+        });
+
+        let page: Page<Value> = Page::parse(raw, "toptags", "tag").unwrap();
+        assert_eq!(page.items.len(), 3);
+        assert_eq!(page.page, 1);
+        assert_eq!(page.total_pages, 1);
+        assert_eq!(page.total, 3);
+    }
+
+    #[test]
+    fn it_parses_a_search_page() {
+        let raw = json!({
+          "results": {
+            "opensearch:Query": { "#text": "", "role": "request", "searchTerms": "comete", "startPage": "2" },
+            "opensearch:totalResults": "101",
+            "opensearch:startIndex": "50",
+            "opensearch:itemsPerPage": "50",
+            "artistmatches": { "artist": [{ "name": "Comete" }] },
+            "@attr": { "for": "comete" }
+          }
+        });
+
+        let page: Page<Value> = Page::parse(raw, "results", "artistmatches.artist").unwrap();
+        assert_eq!(page.items, vec![json!({ "name": "Comete" })]);
+        assert_eq!(page.page, 2);
+        assert_eq!(page.total_pages, 3);
+        assert_eq!(page.total, 101);
+    }
+}
@@ -78,4 +78,66 @@ mod tests {
         let recent_tracks_page: RecentTracksPage = serde_json::from_str(json_data).unwrap();
         insta::assert_debug_snapshot!(recent_tracks_page);
     }
+
+    #[test]
+    fn it_preserves_loved_and_streamable_flags() {
+        let json_value = serde_json::json!({
+          "recenttracks": {
+            "track": [
+              {
+                "artist": { "url": "https://www.last.fm/music/Comete", "name": "Comete", "image": [], "mbid": "" },
+                "mbid": "",
+                "name": "Antenna",
+                "image": [],
+                "streamable": "1",
+                "album": { "mbid": "", "#text": "Solo cose belle" },
+                "url": "https://www.last.fm/music/Comete/_/Antenna",
+                "@attr": { "nowplaying": "true" },
+                "loved": "1"
+              },
+              {
+                "artist": { "url": "https://www.last.fm/music/Comete", "name": "Comete", "image": [], "mbid": "" },
+                "mbid": "",
+                "name": "Cornflakes",
+                "image": [],
+                "streamable": "0",
+                "album": { "mbid": "", "#text": "Solo cose belle" },
+                "url": "https://www.last.fm/music/Comete/_/Cornflakes",
+                "date": { "uts": "1676284092", "#text": "13 Feb 2023, 10:28" },
+                "loved": "1"
+              },
+              {
+                "artist": { "url": "https://www.last.fm/music/Comete", "name": "Comete", "image": [], "mbid": "" },
+                "mbid": "",
+                "name": "Bella",
+                "image": [],
+                "streamable": "1",
+                "album": { "mbid": "", "#text": "Solo cose belle" },
+                "url": "https://www.last.fm/music/Comete/_/Bella",
+                "date": { "uts": "1676283792", "#text": "13 Feb 2023, 10:23" },
+                "loved": "0"
+              }
+            ],
+            "@attr": { "perPage": "200", "totalPages": "1", "page": "1", "user": "loige", "total": "2" }
+          }
+        });
+
+        let recent_tracks_page: RecentTracksPage = serde_json::from_value(json_value).unwrap();
+        let flags: Vec<(String, bool, bool)> = recent_tracks_page
+            .tracks
+            .into_iter()
+            .map(|t| match t {
+                Track::NowPlaying(t) => (t.name, t.loved, t.streamable),
+                Track::Recorded(t) => (t.name, t.loved, t.streamable),
+            })
+            .collect();
+        assert_eq!(
+            flags,
+            vec![
+                ("Antenna".to_string(), true, true),
+                ("Cornflakes".to_string(), true, false),
+                ("Bella".to_string(), false, true),
+            ]
+        );
+    }
 }
@@ -0,0 +1,317 @@
+//! # Request
+//!
+//! The shared request layer used by every call to the Last.fm API.
+//!
+//! It takes care of adding the API key, signing requests that need authentication
+//! (see [`sign`]), retrying failed attempts and decoding error responses.
+use crate::{error_response::ErrorResponse, errors::Error, retry_strategy::RetryStrategy};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::sync::Arc;
+use url::Url;
+
+/// Computes the `api_sig` for the given set of parameters.
+///
+/// Last.fm signs a request by sorting all its parameters by name, concatenating every name
+/// with its value, appending the API secret and hashing the resulting string with MD5.
+/// The `format` and `callback` parameters are never part of the signature.
+///
+/// See <https://www.last.fm/api/authspec#_8-signing-calls> for more details.
+///
+/// ```rust
+/// use lastfm::request::sign;
+///
+/// let api_sig = sign(&[("method", "auth.getToken"), ("api_key", "xxxxxxxx")], "secret");
+/// assert_eq!(api_sig.len(), 32);
+/// ```
+pub fn sign<K: AsRef<str>, V: AsRef<str>>(params: &[(K, V)], api_secret: &str) -> String {
+    let mut sorted: Vec<(&str, &str)> = params
+        .iter()
+        .map(|(k, v)| (k.as_ref(), v.as_ref()))
+        .filter(|(k, _)| *k != "format" && *k != "callback")
+        .collect();
+    sorted.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut payload = String::new();
+    for (k, v) in sorted {
+        payload.push_str(k);
+        payload.push_str(v);
+    }
+    payload.push_str(api_secret);
+
+    format!("{:x}", md5::compute(payload))
+}
+
+/// How a request needs to be authenticated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Auth {
+    /// Only the API key is sent.
+    None,
+    /// The request is signed with the API secret (e.g. `auth.getToken`).
+    Signed,
+    /// The request is signed and carries the session key of the authenticated user.
+    Session,
+}
+
+/// The parameters of a request (excluding `method`, `api_key`, `sk`, `api_sig` and `format`
+/// which are added by the [`Transport`]).
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Params(pub(crate) Vec<(String, String)>);
+
+impl Params {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a parameter.
+    pub(crate) fn with(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+        self.push(key, value);
+        self
+    }
+
+    /// Adds a parameter only if a value is present.
+    pub(crate) fn with_opt(mut self, key: impl Into<String>, value: Option<impl ToString>) -> Self {
+        if let Some(value) = value {
+            self.push(key, value);
+        }
+        self
+    }
+
+    /// Adds a parameter in place.
+    pub(crate) fn push(&mut self, key: impl Into<String>, value: impl ToString) {
+        self.0.push((key.into(), value.to_string()));
+    }
+}
+
+/// The options shared by the queries identifying an artist, album or track
+/// (e.g. [`ArtistQuery`](crate::artist_info::ArtistQuery)).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct QueryOptions {
+    /// Whether Last.fm should correct misspelled names.
+    pub(crate) autocorrect: bool,
+    /// The user whose statistics are included in `*.getInfo` responses.
+    pub(crate) username: Option<String>,
+}
+
+impl QueryOptions {
+    /// Adds the `autocorrect` parameter (accepted by all the `artist.*`, `album.*` and `track.*` methods).
+    pub(crate) fn with_autocorrect(&self, params: Params) -> Params {
+        params.with("autocorrect", u8::from(self.autocorrect))
+    }
+
+    /// Adds the `username` parameter (only accepted by the `*.getInfo` methods), if set.
+    pub(crate) fn with_username(&self, params: Params) -> Params {
+        params.with_opt("username", self.username.as_deref())
+    }
+}
+
+/// Everything needed to send a request to the Last.fm API.
+#[derive(Clone)]
+pub(crate) struct Transport {
+    pub(crate) reqwest_client: reqwest::Client,
+    pub(crate) base_url: Url,
+    pub(crate) retry_strategy: Arc<dyn RetryStrategy>,
+    pub(crate) api_key: String,
+    pub(crate) api_secret: Option<String>,
+    pub(crate) session_key: Option<String>,
+}
+
+impl Transport {
+    /// Performs a `GET` request for the given Last.fm method.
+    pub(crate) async fn get<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Params,
+        auth: Auth,
+    ) -> Result<T, Error> {
+        self.send(reqwest::Method::GET, method, params, auth).await
+    }
+
+    /// Performs a `POST` request for the given Last.fm method.
+    ///
+    /// Write methods (e.g. `track.scrobble`) must always be sent as `POST`.
+    pub(crate) async fn post<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Params,
+        auth: Auth,
+    ) -> Result<T, Error> {
+        self.send(reqwest::Method::POST, method, params, auth).await
+    }
+
+    /// Builds the final list of parameters, adding the API key and, if needed, the session key and the signature.
+    fn prepare(
+        &self,
+        method: &str,
+        params: Params,
+        auth: Auth,
+    ) -> Result<Vec<(String, String)>, Error> {
+        let mut params = params.0;
+        params.push(("method".to_string(), method.to_string()));
+        params.push(("api_key".to_string(), self.api_key.clone()));
+
+        if auth == Auth::Session {
+            let session_key = self.session_key.as_ref().ok_or(Error::MissingSessionKey)?;
+            params.push(("sk".to_string(), session_key.clone()));
+        }
+
+        if auth != Auth::None {
+            let api_secret = self.api_secret.as_ref().ok_or(Error::MissingApiSecret)?;
+            let api_sig = sign(&params, api_secret);
+            params.push(("api_sig".to_string(), api_sig));
+        }
+
+        params.push(("format".to_string(), "json".to_string()));
+
+        Ok(params)
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        http_method: reqwest::Method,
+        method: &str,
+        params: Params,
+        auth: Auth,
+    ) -> Result<T, Error> {
+        let params = self.prepare(method, params, auth)?;
+
+        let mut errors: Vec<Error> = Vec::new();
+        let mut num_retries: usize = 0;
+        while let Some(retry_delay) = self.retry_strategy.should_retry_after(num_retries) {
+            let req = if http_method == reqwest::Method::POST {
+                self.reqwest_client
+                    .post(self.base_url.as_str())
+                    .form(&params)
+            } else {
+                let url = Url::parse_with_params(self.base_url.as_str(), &params).unwrap();
+                self.reqwest_client.get(url.to_string())
+            };
+
+            match req.send().await {
+                Ok(res) => {
+                    let body: Value = res.json().await.map_err(|e| redact(e, auth))?;
+                    if body.get("error").is_none() {
+                        return Ok(serde_json::from_value(body)?);
+                    }
+                    let e: ErrorResponse = serde_json::from_value(body)?;
+                    tracing::error!("LastFm Error: {}", e.message);
+                    if !e.is_retriable() {
+                        return Err(e.into());
+                    }
+                    tokio::time::sleep(retry_delay).await;
+                }
+                Err(e) => {
+                    let e = redact(e, auth);
+                    tracing::error!("Error: {}", e);
+                    errors.push(e.into());
+                    tokio::time::sleep(retry_delay).await;
+                }
+            }
+            num_retries += 1;
+        }
+
+        Err(Error::TooManyRetry(errors))
+    }
+}
+
+/// Strips the URL from the errors of authenticated requests, since it carries the session key
+/// and the signature, so that they are never logged nor returned to the caller.
+fn redact(e: reqwest::Error, auth: Auth) -> reqwest::Error {
+    if auth == Auth::None {
+        e
+    } else {
+        e.without_url()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::retry_strategy::JitteredBackoff;
+
+    fn transport(api_secret: Option<&str>, session_key: Option<&str>) -> Transport {
+        Transport {
+            reqwest_client: reqwest::Client::new(),
+            base_url: "http://localhost".parse().unwrap(),
+            retry_strategy: Arc::new(JitteredBackoff::default()),
+            api_key: "xxxxxxxx".to_string(),
+            api_secret: api_secret.map(str::to_string),
+            session_key: session_key.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn it_signs_sorted_params_and_skips_format() {
+        let params = [
+            ("token", "yyyyyyyy"),
+            ("method", "auth.getSession"),
+            ("format", "json"),
+            ("api_key", "xxxxxxxx"),
+        ];
+
+        assert_eq!(sign(&params, "secret"), "f813353710a6660b9178acc63940366c");
+    }
+
+    #[test]
+    fn it_adds_session_key_and_signature() {
+        let params = transport(Some("secret"), Some("session"))
+            .prepare(
+                "track.love",
+                Params::new().with("track", "Song"),
+                Auth::Session,
+            )
+            .unwrap();
+        let keys: Vec<&str> = params.iter().map(|(k, _)| k.as_str()).collect();
+
+        assert_eq!(
+            keys,
+            vec!["track", "method", "api_key", "sk", "api_sig", "format"]
+        );
+    }
+
+    #[test]
+    fn it_requires_credentials_for_authenticated_calls() {
+        let res = transport(None, None).prepare("auth.getToken", Params::new(), Auth::Signed);
+        assert!(matches!(res, Err(Error::MissingApiSecret)));
+
+        let res =
+            transport(Some("secret"), None).prepare("track.love", Params::new(), Auth::Session);
+        assert!(matches!(res, Err(Error::MissingSessionKey)));
+
+        let params = transport(None, None)
+            .prepare("user.getRecentTracks", Params::new(), Auth::None)
+            .unwrap();
+        assert!(params.iter().all(|(k, _)| k != "api_sig"));
+    }
+
+    #[tokio::test]
+    async fn it_redacts_the_url_of_authenticated_requests() {
+        let e = reqwest::Client::new()
+            .get("http://[::1]:1/?sk=session&api_sig=signature")
+            .send()
+            .await
+            .unwrap_err();
+        assert!(e.url().is_some());
+        assert!(redact(e, Auth::Session).url().is_none());
+    }
+
+    #[test]
+    fn it_adds_the_query_options() {
+        let options = QueryOptions::default();
+        let params = options.with_username(options.with_autocorrect(Params::new()));
+        assert_eq!(params.0, vec![("autocorrect".to_string(), "0".to_string())]);
+
+        let options = QueryOptions {
+            autocorrect: true,
+            username: Some("loige".to_string()),
+        };
+        let params = options.with_username(options.with_autocorrect(Params::new()));
+        assert_eq!(
+            params.0,
+            vec![
+                ("autocorrect".to_string(), "1".to_string()),
+                ("username".to_string(), "loige".to_string())
+            ]
+        );
+    }
+}
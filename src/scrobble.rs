@@ -0,0 +1,531 @@
+//! # Scrobble
+//!
+//! Defines the [`Scrobble`] struct and the methods to add tracks to a user's listening history
+//! or to update the track they are currently playing (see [`NowPlayingUpdate`]).
+//!
+//! ```rust,no_run
+//! # use lastfm::{scrobble::Scrobble, Client};
+//! # use chrono::Utc;
+//! #
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!   let client = Client::<String, &str>::from_env("YOUR_USERNAME");
+//!   let scrobble = Scrobble::builder()
+//!     .artist("The Rocket Summer")
+//!     .track("Do You Feel")
+//!     .timestamp(Utc::now())
+//!     .album("Do You Feel")
+//!     .build();
+//!
+//!   let result = client.scrobble(&[scrobble]).await?;
+//!   println!("Accepted: {}, ignored: {}", result.accepted, result.ignored);
+//!
+//!   Ok(())
+//! }
+//! ```
+//!
+//! See <https://www.last.fm/api/show/track.scrobble> and <https://www.last.fm/api/show/track.updateNowPlaying>
+//! for more details.
+use crate::{
+    errors::Error,
+    request::{Auth, Params},
+    serde_helpers::{bool_from_any, datetime_from_uts, number_from_any, one_or_many},
+    track::NowPlayingTrack,
+    Client,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::future::Future;
+use typed_builder::TypedBuilder;
+
+/// The maximum number of scrobbles that can be sent in a single `track.scrobble` request.
+pub const MAX_SCROBBLES_PER_REQUEST: usize = 50;
+
+/// A track to add to the user's listening history.
+#[derive(TypedBuilder, Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Scrobble {
+    /// The artist name.
+    #[builder(setter(into))]
+    pub artist: String,
+    /// The track name.
+    #[builder(setter(into))]
+    pub track: String,
+    /// The time the track started playing.
+    pub timestamp: DateTime<Utc>,
+    /// The album name.
+    #[builder(default, setter(strip_option, into))]
+    pub album: Option<String>,
+    /// The album artist, if different from the track artist.
+    #[builder(default, setter(strip_option, into))]
+    pub album_artist: Option<String>,
+    /// The length of the track in seconds.
+    #[builder(default, setter(strip_option))]
+    pub duration: Option<u32>,
+    /// The track number of the track on the album.
+    #[builder(default, setter(strip_option))]
+    pub track_number: Option<u32>,
+    /// The MusicBrainz track ID.
+    #[builder(default, setter(strip_option, into))]
+    pub mbid: Option<String>,
+    /// Whether the user chose the track (`true`) or it was picked by a radio or recommendation service (`false`).
+    #[builder(default, setter(strip_option))]
+    pub chosen_by_user: Option<bool>,
+}
+
+impl Scrobble {
+    /// Adds the fields of this scrobble to the given params using the indexed form (e.g. `artist[0]`).
+    fn append_params(&self, index: usize, params: &mut Params) {
+        params.push(format!("artist[{index}]"), &self.artist);
+        params.push(format!("track[{index}]"), &self.track);
+        params.push(format!("timestamp[{index}]"), self.timestamp.timestamp());
+        if let Some(album) = &self.album {
+            params.push(format!("album[{index}]"), album);
+        }
+        if let Some(album_artist) = &self.album_artist {
+            params.push(format!("albumArtist[{index}]"), album_artist);
+        }
+        if let Some(duration) = self.duration {
+            params.push(format!("duration[{index}]"), duration);
+        }
+        if let Some(track_number) = self.track_number {
+            params.push(format!("trackNumber[{index}]"), track_number);
+        }
+        if let Some(mbid) = &self.mbid {
+            params.push(format!("mbid[{index}]"), mbid);
+        }
+        if let Some(chosen_by_user) = self.chosen_by_user {
+            params.push(format!("chosenByUser[{index}]"), u8::from(chosen_by_user));
+        }
+    }
+}
+
+/// A value echoed back by Last.fm, which might have been corrected (e.g. a misspelled artist name).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CorrectedValue {
+    /// The value as recorded by Last.fm (empty if not provided).
+    #[serde(rename = "#text", default)]
+    pub value: String,
+    /// Whether Last.fm corrected the value that was sent.
+    #[serde(deserialize_with = "bool_from_any")]
+    pub corrected: bool,
+}
+
+/// The reason why Last.fm ignored a scrobble.
+///
+/// See <https://www.last.fm/api/scrobbling#ignored-messages> for more details.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IgnoredReason {
+    /// The scrobble was not ignored.
+    NotIgnored,
+    /// The artist was ignored.
+    ArtistIgnored,
+    /// The track was ignored.
+    TrackIgnored,
+    /// The timestamp was too old.
+    TimestampTooOld,
+    /// The timestamp was too new.
+    TimestampTooNew,
+    /// The daily scrobble limit was exceeded.
+    DailyScrobbleLimitExceeded,
+    /// An ignored code not known by this library.
+    Unknown(u32),
+}
+
+impl From<u32> for IgnoredReason {
+    fn from(code: u32) -> Self {
+        match code {
+            0 => IgnoredReason::NotIgnored,
+            1 => IgnoredReason::ArtistIgnored,
+            2 => IgnoredReason::TrackIgnored,
+            3 => IgnoredReason::TimestampTooOld,
+            4 => IgnoredReason::TimestampTooNew,
+            5 => IgnoredReason::DailyScrobbleLimitExceeded,
+            code => IgnoredReason::Unknown(code),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for IgnoredReason {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let code: u32 = number_from_any(deserializer)?;
+        Ok(code.into())
+    }
+}
+
+/// Tells if (and why) a scrobble was ignored.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IgnoredMessage {
+    pub code: IgnoredReason,
+    #[serde(rename = "#text", default)]
+    pub message: String,
+}
+
+/// The outcome of a single scrobble, as echoed back by Last.fm.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ScrobbledTrack {
+    pub artist: CorrectedValue,
+    pub track: CorrectedValue,
+    pub album: CorrectedValue,
+    pub album_artist: CorrectedValue,
+    #[serde(deserialize_with = "datetime_from_uts")]
+    pub timestamp: DateTime<Utc>,
+    pub ignored_message: IgnoredMessage,
+}
+
+impl ScrobbledTrack {
+    /// Whether Last.fm ignored this scrobble.
+    pub fn is_ignored(&self) -> bool {
+        self.ignored_message.code != IgnoredReason::NotIgnored
+    }
+}
+
+/// The result of a [`Client::scrobble`] call.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ScrobbleResult {
+    /// The number of scrobbles accepted by Last.fm.
+    pub accepted: u32,
+    /// The number of scrobbles ignored by Last.fm.
+    pub ignored: u32,
+    /// The outcome of every scrobble, in the same order they were sent.
+    pub scrobbles: Vec<ScrobbledTrack>,
+}
+
+impl ScrobbleResult {
+    /// Appends the result of a following batch.
+    pub(crate) fn merge(&mut self, other: ScrobbleResult) {
+        self.accepted += other.accepted;
+        self.ignored += other.ignored;
+        self.scrobbles.extend(other.scrobbles);
+    }
+}
+
+/// The raw response of the `track.scrobble` method.
+#[derive(Deserialize)]
+struct ScrobbleResponse {
+    scrobbles: ScrobbleResponseBody,
+}
+
+#[derive(Deserialize)]
+struct ScrobbleResponseBody {
+    #[serde(rename = "@attr")]
+    attr: ScrobbleResponseAttr,
+    #[serde(deserialize_with = "one_or_many", default)]
+    scrobble: Vec<ScrobbledTrack>,
+}
+
+#[derive(Deserialize)]
+struct ScrobbleResponseAttr {
+    #[serde(deserialize_with = "number_from_any")]
+    accepted: u32,
+    #[serde(deserialize_with = "number_from_any")]
+    ignored: u32,
+}
+
+impl From<ScrobbleResponse> for ScrobbleResult {
+    fn from(res: ScrobbleResponse) -> Self {
+        ScrobbleResult {
+            accepted: res.scrobbles.attr.accepted,
+            ignored: res.scrobbles.attr.ignored,
+            scrobbles: res.scrobbles.scrobble,
+        }
+    }
+}
+
+/// A track to set as currently playing for the authenticated user.
+#[derive(TypedBuilder, Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NowPlayingUpdate {
+    /// The artist name.
+    #[builder(setter(into))]
+    pub artist: String,
+    /// The track name.
+    #[builder(setter(into))]
+    pub track: String,
+    /// The album name.
+    #[builder(default, setter(strip_option, into))]
+    pub album: Option<String>,
+    /// The album artist, if different from the track artist.
+    #[builder(default, setter(strip_option, into))]
+    pub album_artist: Option<String>,
+    /// The length of the track in seconds.
+    #[builder(default, setter(strip_option))]
+    pub duration: Option<u32>,
+    /// The track number of the track on the album.
+    #[builder(default, setter(strip_option))]
+    pub track_number: Option<u32>,
+    /// The MusicBrainz track ID.
+    #[builder(default, setter(strip_option, into))]
+    pub mbid: Option<String>,
+}
+
+impl NowPlayingUpdate {
+    fn params(&self) -> Params {
+        Params::new()
+            .with("artist", &self.artist)
+            .with("track", &self.track)
+            .with_opt("album", self.album.as_ref())
+            .with_opt("albumArtist", self.album_artist.as_ref())
+            .with_opt("duration", self.duration)
+            .with_opt("trackNumber", self.track_number)
+            .with_opt("mbid", self.mbid.as_ref())
+    }
+}
+
+/// Creates an update from a track fetched with [`Client::now_playing`] (e.g. to mirror it to another account).
+impl From<&NowPlayingTrack> for NowPlayingUpdate {
+    fn from(track: &NowPlayingTrack) -> Self {
+        NowPlayingUpdate {
+            artist: track.artist.name.clone(),
+            track: track.name.clone(),
+            album: Some(track.album.name.clone()).filter(|album| !album.is_empty()),
+            album_artist: None,
+            duration: None,
+            track_number: None,
+            mbid: track.mbid.clone(),
+        }
+    }
+}
+
+/// The track set as currently playing, as echoed back by Last.fm.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct NowPlayingResult {
+    pub artist: CorrectedValue,
+    pub track: CorrectedValue,
+    pub album: CorrectedValue,
+    pub album_artist: CorrectedValue,
+    pub ignored_message: IgnoredMessage,
+}
+
+/// The raw response of the `track.updateNowPlaying` method.
+#[derive(Deserialize)]
+struct NowPlayingResponse {
+    nowplaying: NowPlayingResult,
+}
+
+/// Builds the params for a single `track.scrobble` request.
+fn scrobble_params(batch: &[Scrobble]) -> Params {
+    let mut params = Params::new();
+    for (index, scrobble) in batch.iter().enumerate() {
+        scrobble.append_params(index, &mut params);
+    }
+    params
+}
+
+/// Sends the given scrobbles in batches of (at most) [`MAX_SCROBBLES_PER_REQUEST`] with `send_batch`,
+/// merging the results.
+///
+/// If a batch fails after some batches were already sent, the error is wrapped in
+/// [`Error::PartialScrobble`] together with the results of the sent batches.
+async fn scrobble_in_batches<'a, F, Fut>(
+    scrobbles: &'a [Scrobble],
+    mut send_batch: F,
+) -> Result<ScrobbleResult, Error>
+where
+    F: FnMut(&'a [Scrobble]) -> Fut,
+    Fut: Future<Output = Result<ScrobbleResult, Error>>,
+{
+    let mut result = ScrobbleResult::default();
+    let mut sent = 0;
+
+    for batch in scrobbles.chunks(MAX_SCROBBLES_PER_REQUEST) {
+        match send_batch(batch).await {
+            Ok(res) => result.merge(res),
+            Err(e) if sent == 0 => return Err(e),
+            Err(e) => {
+                return Err(Error::PartialScrobble {
+                    result,
+                    sent,
+                    source: Box::new(e),
+                })
+            }
+        }
+        sent += batch.len();
+    }
+
+    Ok(result)
+}
+
+impl<A: AsRef<str>, U: AsRef<str>> Client<A, U> {
+    /// Adds the given tracks to the listening history of the authenticated user (`track.scrobble`).
+    ///
+    /// Scrobbles are sent in batches of (at most) [`MAX_SCROBBLES_PER_REQUEST`] and the results of
+    /// all the batches are merged together. If a batch fails, the following batches are not sent:
+    /// if it was the first batch, its error is returned as is, otherwise an [`Error::PartialScrobble`]
+    /// is returned with the results of the batches already recorded by Last.fm and the index of the
+    /// first scrobble that was not recorded (so that only `&scrobbles[sent..]` needs to be retried).
+    ///
+    /// Requires the client to be configured with an `api_secret` and a `session_key`.
+    pub async fn scrobble(&self, scrobbles: &[Scrobble]) -> Result<ScrobbleResult, Error> {
+        let transport = self.transport();
+
+        scrobble_in_batches(scrobbles, |batch| {
+            let transport = &transport;
+            async move {
+                let res: ScrobbleResponse = transport
+                    .post("track.scrobble", scrobble_params(batch), Auth::Session)
+                    .await?;
+                Ok(res.into())
+            }
+        })
+        .await
+    }
+
+    /// Sets the track the authenticated user is currently playing (`track.updateNowPlaying`).
+    ///
+    /// Requires the client to be configured with an `api_secret` and a `session_key`.
+    pub async fn update_now_playing(
+        &self,
+        update: &NowPlayingUpdate,
+    ) -> Result<NowPlayingResult, Error> {
+        let res: NowPlayingResponse = self
+            .transport()
+            .post("track.updateNowPlaying", update.params(), Auth::Session)
+            .await?;
+
+        Ok(res.nowplaying)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn scrobbles(count: usize) -> Vec<Scrobble> {
+        (0..count)
+            .map(|i| {
+                Scrobble::builder()
+                    .artist("Comete")
+                    .track(format!("Track {i}"))
+                    .timestamp(Utc.timestamp_opt(1676284092 + i as i64, 0).unwrap())
+                    .build()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn it_returns_the_partial_result_when_a_batch_fails() {
+        let scrobbles = scrobbles(120);
+        let mut calls = 0;
+        let res = scrobble_in_batches(&scrobbles, |batch| {
+            calls += 1;
+            let call = calls;
+            async move {
+                match call {
+                    3 => Err(Error::MissingSessionKey),
+                    _ => Ok(ScrobbleResult {
+                        accepted: batch.len() as u32,
+                        ..Default::default()
+                    }),
+                }
+            }
+        })
+        .await;
+
+        match res {
+            Err(Error::PartialScrobble {
+                result,
+                sent,
+                source,
+            }) => {
+                assert_eq!(sent, 100);
+                assert_eq!(result.accepted, 100);
+                assert!(matches!(*source, Error::MissingSessionKey));
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_returns_the_error_as_is_when_the_first_batch_fails() {
+        let scrobbles = scrobbles(10);
+        let res =
+            scrobble_in_batches(&scrobbles, |_| async { Err(Error::MissingSessionKey) }).await;
+
+        assert!(matches!(res, Err(Error::MissingSessionKey)));
+    }
+
+    #[test]
+    fn it_builds_indexed_params() {
+        let batch = vec![
+            Scrobble::builder()
+                .artist("Comete")
+                .track("Antenna")
+                .timestamp(Utc.timestamp_opt(1676284092, 0).unwrap())
+                .build(),
+            Scrobble::builder()
+                .artist("The Rocket Summer")
+                .track("Do You Feel")
+                .timestamp(Utc.timestamp_opt(1676284392, 0).unwrap())
+                .album("Do You Feel")
+                .duration(215)
+                .chosen_by_user(true)
+                .build(),
+        ];
+
+        let params = scrobble_params(&batch);
+        let params: Vec<(&str, &str)> = params
+            .0
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+
+        assert_eq!(
+            params,
+            vec![
+                ("artist[0]", "Comete"),
+                ("track[0]", "Antenna"),
+                ("timestamp[0]", "1676284092"),
+                ("artist[1]", "The Rocket Summer"),
+                ("track[1]", "Do You Feel"),
+                ("timestamp[1]", "1676284392"),
+                ("album[1]", "Do You Feel"),
+                ("duration[1]", "215"),
+                ("chosenByUser[1]", "1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_deserializes_a_scrobble_response() {
+        let json_data = include_str!("fixtures/scrobble_response.json");
+
+        let res: ScrobbleResponse = serde_json::from_str(json_data).unwrap();
+        let result: ScrobbleResult = res.into();
+        insta::assert_debug_snapshot!(result);
+    }
+
+    #[test]
+    fn it_deserializes_a_now_playing_response() {
+        let json_value = serde_json::json!({
+          "nowplaying": {
+            "artist": { "corrected": "0", "#text": "Comete" },
+            "track": { "corrected": "1", "#text": "Antenna" },
+            "album": { "corrected": "0", "#text": "" },
+            "albumArtist": { "corrected": "0", "#text": "" },
+            "ignoredMessage": { "code": "0", "#text": "" }
+          }
+        });
+
+        let res: NowPlayingResponse = serde_json::from_value(json_value).unwrap();
+        assert_eq!(res.nowplaying.artist.value, "Comete");
+        assert!(res.nowplaying.track.corrected);
+        assert_eq!(res.nowplaying.ignored_message.code, IgnoredReason::NotIgnored);
+    }
+
+    #[test]
+    fn it_deserializes_a_single_scrobble_response() {
+        let json_data = include_str!("fixtures/single_scrobble_response.json");
+
+        let res: ScrobbleResponse = serde_json::from_str(json_data).unwrap();
+        let result: ScrobbleResult = res.into();
+        assert_eq!(result.accepted, 1);
+        assert_eq!(result.scrobbles.len(), 1);
+        assert!(!result.scrobbles[0].is_ignored());
+        assert_eq!(result.scrobbles[0].album.value, "");
+    }
+}
@@ -0,0 +1,556 @@
+//! # Scrobble queue
+//!
+//! Defines the [`ScrobbleQueue`] struct, a durable queue of pending scrobbles for devices that can go offline.
+//!
+//! Scrobbles are appended to a local file (one JSON object per line) and sent later,
+//! in order, with [`ScrobbleQueue::flush`].
+//!
+//! ```rust,no_run
+//! # use lastfm::{scrobble::Scrobble, scrobble_queue::ScrobbleQueue, Client};
+//! # use chrono::Utc;
+//! #
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!   let client = Client::<String, &str>::from_env("YOUR_USERNAME");
+//!   let queue = ScrobbleQueue::new("scrobbles.jsonl");
+//!
+//!   queue
+//!     .push(&Scrobble::builder().artist("Comete").track("Antenna").timestamp(Utc::now()).build())
+//!     .await?;
+//!
+//!   // ... later, when the device is back online
+//!   let result = queue.flush(&client).await?;
+//!   println!("Accepted: {}, pending: {}", result.accepted, queue.pending().await?.len());
+//!
+//!   Ok(())
+//! }
+//! ```
+use crate::{
+    errors::Error,
+    scrobble::{
+        IgnoredReason, Scrobble, ScrobbleResult, ScrobbledTrack, MAX_SCROBBLES_PER_REQUEST,
+    },
+    Client,
+};
+use std::{
+    io::{ErrorKind, SeekFrom},
+    path::{Path, PathBuf},
+};
+use tokio::{
+    fs,
+    io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt},
+    sync::Mutex,
+};
+
+/// How many bytes are read at a time while looking for the start of a cut-off last line.
+const TAIL_CHUNK_SIZE: u64 = 1024;
+
+/// A queue of pending scrobbles persisted to a local file (JSON lines).
+///
+/// A single [`ScrobbleQueue`] can be shared between tasks, but there must be only one instance
+/// (and one process) using a given file at any time: the file is not locked, so scrobbles pushed
+/// through another instance while this one is flushing could be lost.
+#[derive(Debug)]
+pub struct ScrobbleQueue {
+    path: PathBuf,
+    /// Guards every access to the file.
+    lock: Mutex<()>,
+    /// Makes sure the same scrobbles are never sent by two concurrent flushes.
+    flushing: Mutex<()>,
+}
+
+/// Whether a scrobble should stay in the queue after Last.fm processed it.
+///
+/// Scrobbles ignored because of the daily limit are kept to be retried later,
+/// while scrobbles ignored for any other reason are permanently rejected and are dropped.
+fn should_keep(track: &ScrobbledTrack) -> bool {
+    track.ignored_message.code == IgnoredReason::DailyScrobbleLimitExceeded
+}
+
+/// Splits the content of the queue file into its newline terminated lines and the
+/// (possibly cut-off) text after the last newline.
+fn split_tail(data: &str) -> (&str, &str) {
+    match data.rfind('\n') {
+        Some(index) => data.split_at(index + 1),
+        None => ("", data),
+    }
+}
+
+/// Removes the first `batch_size` pending scrobbles once Last.fm processed them, putting back
+/// the ones that should be retried later.
+///
+/// Returns whether some scrobbles were put back because of the daily limit.
+/// If the response does not contain the outcome of every scrobble of the batch, nothing is removed
+/// and an [`Error::IncompleteScrobbleResponse`] is returned.
+fn settle_batch(
+    pending: &mut Vec<Scrobble>,
+    batch_size: usize,
+    res: &ScrobbleResult,
+) -> Result<bool, Error> {
+    if res.scrobbles.len() != batch_size {
+        return Err(Error::IncompleteScrobbleResponse {
+            sent: batch_size,
+            received: res.scrobbles.len(),
+        });
+    }
+
+    let batch: Vec<Scrobble> = pending.drain(..batch_size).collect();
+    let kept: Vec<Scrobble> = batch
+        .into_iter()
+        .zip(res.scrobbles.iter())
+        .filter(|(_, track)| should_keep(track))
+        .map(|(scrobble, _)| scrobble)
+        .collect();
+    let limited = !kept.is_empty();
+    pending.splice(0..0, kept);
+
+    Ok(limited)
+}
+
+impl ScrobbleQueue {
+    /// Creates a new queue backed by the file at the given path.
+    ///
+    /// The file is created on the first [`ScrobbleQueue::push`] if it does not exist.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        ScrobbleQueue {
+            path: path.into(),
+            lock: Mutex::new(()),
+            flushing: Mutex::new(()),
+        }
+    }
+
+    /// The path of the file backing the queue.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends a scrobble to the queue.
+    ///
+    /// If a previous `push` was interrupted (e.g. by a power loss) and left a cut-off line at the end
+    /// of the file, that line is discarded first.
+    pub async fn push(&self, scrobble: &Scrobble) -> Result<(), Error> {
+        let _guard = self.lock.lock().await;
+        self.repair_tail().await?;
+
+        let mut line = serde_json::to_string(scrobble)?;
+        line.push('\n');
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        file.sync_all().await?;
+
+        Ok(())
+    }
+
+    /// Returns all the pending scrobbles, in the order they were added.
+    pub async fn pending(&self) -> Result<Vec<Scrobble>, Error> {
+        let _guard = self.lock.lock().await;
+        self.read().await
+    }
+
+    /// Sends all the pending scrobbles, in order, in batches of [`MAX_SCROBBLES_PER_REQUEST`].
+    ///
+    /// Failed requests are retried according to the client's retry strategy.
+    /// After every batch, the queue file is updated so that the sent scrobbles are never sent twice:
+    /// scrobbles that Last.fm accepted or permanently ignored are removed, while scrobbles ignored
+    /// because of the daily scrobble limit are kept (and the flush stops, as any further batch would
+    /// be ignored too).
+    ///
+    /// If a batch fails, or Last.fm does not report the outcome of every scrobble of the batch,
+    /// the error is returned and that batch (and all the following ones) stay in the queue.
+    ///
+    /// The queue is not locked while a batch is being sent, so scrobbles can still be pushed
+    /// (and are sent by the same flush).
+    pub async fn flush<A: AsRef<str>, U: AsRef<str>>(
+        &self,
+        client: &Client<A, U>,
+    ) -> Result<ScrobbleResult, Error> {
+        let _flushing = self.flushing.lock().await;
+        let mut result = ScrobbleResult::default();
+
+        loop {
+            let batch: Vec<Scrobble> = {
+                let _guard = self.lock.lock().await;
+                let mut pending = self.read().await?;
+                pending.truncate(MAX_SCROBBLES_PER_REQUEST);
+                pending
+            };
+            if batch.is_empty() {
+                break;
+            }
+
+            let res = client.scrobble(&batch).await?;
+            let limited = self.settle(&batch, &res).await?;
+            result.merge(res);
+
+            if limited {
+                break;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Settles the batch at the start of the queue (see [`settle_batch`]) once Last.fm processed it,
+    /// keeping the scrobbles pushed while the batch was being sent.
+    ///
+    /// The file is left untouched if it no longer starts with the batch (i.e. it was changed
+    /// by someone else).
+    async fn settle(&self, batch: &[Scrobble], res: &ScrobbleResult) -> Result<bool, Error> {
+        let _guard = self.lock.lock().await;
+
+        let mut pending = self.read().await?;
+        if !pending.starts_with(batch) {
+            return Err(std::io::Error::other(format!(
+                "{:?} was changed while flushing",
+                self.path
+            ))
+            .into());
+        }
+        let limited = settle_batch(&mut pending, batch.len(), res)?;
+        self.write(&pending).await?;
+
+        Ok(limited)
+    }
+
+    async fn read(&self) -> Result<Vec<Scrobble>, Error> {
+        let data = match fs::read_to_string(&self.path).await {
+            Ok(data) => data,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let (complete, tail) = split_tail(&data);
+        let mut scrobbles = complete
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str::<Scrobble>(line).map_err(Error::from))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if !tail.trim().is_empty() {
+            match serde_json::from_str::<Scrobble>(tail) {
+                Ok(scrobble) => scrobbles.push(scrobble),
+                Err(e) => tracing::warn!("Skipping cut-off scrobble in {:?}: {}", self.path, e),
+            }
+        }
+
+        Ok(scrobbles)
+    }
+
+    /// Makes sure the file ends with a newline before appending to it: a cut-off last line is
+    /// terminated if it holds a valid scrobble, or removed otherwise.
+    ///
+    /// Only the last byte is read when the file is intact.
+    async fn repair_tail(&self) -> Result<(), Error> {
+        let mut file = match fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&self.path)
+            .await
+        {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let len = file.metadata().await?.len();
+        if len == 0 {
+            return Ok(());
+        }
+        file.seek(SeekFrom::End(-1)).await?;
+        if file.read_u8().await? == b'\n' {
+            return Ok(());
+        }
+
+        // Look backwards for the newline terminating the last complete line.
+        let mut start = len;
+        while start > 0 {
+            let chunk_start = start.saturating_sub(TAIL_CHUNK_SIZE);
+            let mut chunk = vec![0; (start - chunk_start) as usize];
+            file.seek(SeekFrom::Start(chunk_start)).await?;
+            file.read_exact(&mut chunk).await?;
+            if let Some(index) = chunk.iter().rposition(|byte| *byte == b'\n') {
+                start = chunk_start + index as u64 + 1;
+                break;
+            }
+            start = chunk_start;
+        }
+
+        let mut tail = Vec::new();
+        file.seek(SeekFrom::Start(start)).await?;
+        file.read_to_end(&mut tail).await?;
+
+        if serde_json::from_slice::<Scrobble>(&tail).is_ok() {
+            file.seek(SeekFrom::End(0)).await?;
+            file.write_all(b"\n").await?;
+        } else {
+            tracing::warn!("Removing cut-off scrobble from {:?}", self.path);
+            file.set_len(start).await?;
+        }
+        file.sync_all().await?;
+
+        Ok(())
+    }
+
+    /// Atomically replaces the content of the queue file with the given scrobbles.
+    async fn write(&self, scrobbles: &[Scrobble]) -> Result<(), Error> {
+        let mut data = String::new();
+        for scrobble in scrobbles {
+            data.push_str(&serde_json::to_string(scrobble)?);
+            data.push('\n');
+        }
+
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+
+        let mut file = fs::File::create(&tmp_path).await?;
+        file.write_all(data.as_bytes()).await?;
+        file.sync_all().await?;
+        fs::rename(&tmp_path, &self.path).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scrobble::{CorrectedValue, IgnoredMessage};
+    use chrono::{TimeZone, Utc};
+
+    fn scrobble(track: &str, uts: i64) -> Scrobble {
+        Scrobble::builder()
+            .artist("Comete")
+            .track(track)
+            .timestamp(Utc.timestamp_opt(uts, 0).unwrap())
+            .build()
+    }
+
+    fn scrobbled_track(code: u32) -> ScrobbledTrack {
+        let value = CorrectedValue {
+            value: String::new(),
+            corrected: false,
+        };
+        ScrobbledTrack {
+            artist: value.clone(),
+            track: value.clone(),
+            album: value.clone(),
+            album_artist: value,
+            timestamp: Utc.timestamp_opt(1676284092, 0).unwrap(),
+            ignored_message: IgnoredMessage {
+                code: code.into(),
+                message: String::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn it_keeps_only_rate_limited_scrobbles() {
+        assert!(!should_keep(&scrobbled_track(0)));
+        assert!(!should_keep(&scrobbled_track(1)));
+        assert!(!should_keep(&scrobbled_track(3)));
+        assert!(should_keep(&scrobbled_track(5)));
+    }
+
+    #[test]
+    fn it_settles_a_batch() {
+        let mut pending = vec![
+            scrobble("Antenna", 1676284092),
+            scrobble("Cornflakes", 1676284392),
+            scrobble("Bella", 1676284692),
+        ];
+        let res = ScrobbleResult {
+            accepted: 1,
+            ignored: 1,
+            scrobbles: vec![scrobbled_track(0), scrobbled_track(5)],
+        };
+
+        assert!(settle_batch(&mut pending, 2, &res).unwrap());
+        assert_eq!(
+            pending,
+            vec![
+                scrobble("Cornflakes", 1676284392),
+                scrobble("Bella", 1676284692)
+            ]
+        );
+    }
+
+    #[test]
+    fn it_keeps_the_batch_when_the_response_is_incomplete() {
+        let original = vec![
+            scrobble("Antenna", 1676284092),
+            scrobble("Cornflakes", 1676284392),
+        ];
+
+        for scrobbles in [vec![scrobbled_track(0)], vec![]] {
+            let mut pending = original.clone();
+            let res = ScrobbleResult {
+                accepted: 2,
+                ignored: 0,
+                scrobbles,
+            };
+
+            assert!(matches!(
+                settle_batch(&mut pending, 2, &res),
+                Err(Error::IncompleteScrobbleResponse { sent: 2, .. })
+            ));
+            assert_eq!(pending, original);
+        }
+    }
+
+    #[tokio::test]
+    async fn it_persists_pending_scrobbles_in_order() {
+        let path = std::env::temp_dir().join(format!(
+            "lastfm-scrobble-queue-{}.jsonl",
+            rand::random::<u64>()
+        ));
+        let queue = ScrobbleQueue::new(&path);
+        assert!(queue.pending().await.unwrap().is_empty());
+
+        queue.push(&scrobble("Antenna", 1676284092)).await.unwrap();
+        queue
+            .push(&scrobble("Cornflakes", 1676284392))
+            .await
+            .unwrap();
+
+        let reopened = ScrobbleQueue::new(&path);
+        assert_eq!(
+            reopened.pending().await.unwrap(),
+            vec![
+                scrobble("Antenna", 1676284092),
+                scrobble("Cornflakes", 1676284392)
+            ]
+        );
+
+        reopened.write(&[]).await.unwrap();
+        assert!(reopened.pending().await.unwrap().is_empty());
+        fs::remove_file(&path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_recovers_from_a_cut_off_line() {
+        let path = std::env::temp_dir().join(format!(
+            "lastfm-scrobble-queue-{}.jsonl",
+            rand::random::<u64>()
+        ));
+        let queue = ScrobbleQueue::new(&path);
+        queue.push(&scrobble("Antenna", 1676284092)).await.unwrap();
+
+        let line = serde_json::to_string(&scrobble("Cornflakes", 1676284392)).unwrap();
+        let mut file = fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .await
+            .unwrap();
+        file.write_all(&line.as_bytes()[..line.len() / 2])
+            .await
+            .unwrap();
+        drop(file);
+
+        assert_eq!(
+            queue.pending().await.unwrap(),
+            vec![scrobble("Antenna", 1676284092)]
+        );
+
+        queue.push(&scrobble("Bella", 1676284692)).await.unwrap();
+        assert_eq!(
+            queue.pending().await.unwrap(),
+            vec![
+                scrobble("Antenna", 1676284092),
+                scrobble("Bella", 1676284692)
+            ]
+        );
+        fs::remove_file(&path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_keeps_a_complete_line_missing_its_newline() {
+        let path = std::env::temp_dir().join(format!(
+            "lastfm-scrobble-queue-{}.jsonl",
+            rand::random::<u64>()
+        ));
+        let line = serde_json::to_string(&scrobble("Antenna", 1676284092)).unwrap();
+        fs::write(&path, line).await.unwrap();
+
+        let queue = ScrobbleQueue::new(&path);
+        queue.push(&scrobble("Bella", 1676284692)).await.unwrap();
+        assert_eq!(
+            queue.pending().await.unwrap(),
+            vec![
+                scrobble("Antenna", 1676284092),
+                scrobble("Bella", 1676284692)
+            ]
+        );
+        fs::remove_file(&path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_removes_a_cut_off_line_longer_than_a_chunk() {
+        let path = std::env::temp_dir().join(format!(
+            "lastfm-scrobble-queue-{}.jsonl",
+            rand::random::<u64>()
+        ));
+        let queue = ScrobbleQueue::new(&path);
+        queue.push(&scrobble("Antenna", 1676284092)).await.unwrap();
+
+        let mut file = fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .await
+            .unwrap();
+        file.write_all(&[b'x'; 3 * TAIL_CHUNK_SIZE as usize])
+            .await
+            .unwrap();
+        drop(file);
+
+        queue.push(&scrobble("Bella", 1676284692)).await.unwrap();
+        assert_eq!(
+            queue.pending().await.unwrap(),
+            vec![
+                scrobble("Antenna", 1676284092),
+                scrobble("Bella", 1676284692)
+            ]
+        );
+        fs::remove_file(&path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_keeps_scrobbles_pushed_while_sending_a_batch() {
+        let path = std::env::temp_dir().join(format!(
+            "lastfm-scrobble-queue-{}.jsonl",
+            rand::random::<u64>()
+        ));
+        let queue = ScrobbleQueue::new(&path);
+        queue.push(&scrobble("Antenna", 1676284092)).await.unwrap();
+        queue
+            .push(&scrobble("Cornflakes", 1676284392))
+            .await
+            .unwrap();
+
+        // The batch is sent without holding the lock, so a new scrobble can be pushed meanwhile.
+        queue.push(&scrobble("Bella", 1676284692)).await.unwrap();
+        let res = ScrobbleResult {
+            accepted: 1,
+            ignored: 1,
+            scrobbles: vec![scrobbled_track(0), scrobbled_track(5)],
+        };
+
+        let batch = [
+            scrobble("Antenna", 1676284092),
+            scrobble("Cornflakes", 1676284392),
+        ];
+        assert!(queue.settle(&batch, &res).await.unwrap());
+        assert_eq!(
+            queue.pending().await.unwrap(),
+            vec![
+                scrobble("Cornflakes", 1676284392),
+                scrobble("Bella", 1676284692)
+            ]
+        );
+        fs::remove_file(&path).await.unwrap();
+    }
+}
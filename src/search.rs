@@ -0,0 +1,213 @@
+//! # Search
+//!
+//! Defines the [`ArtistMatch`], [`AlbumMatch`] and [`TrackMatch`] structs and the methods
+//! to search artists, albums and tracks by name (`artist.search`, `album.search` and `track.search`).
+//!
+//! ```rust,no_run
+//! use futures_util::pin_mut;
+//! use futures_util::stream::StreamExt;
+//! # use lastfm::Client;
+//! #
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!   let client = Client::builder().api_key("YOUR_API_KEY").username("YOUR_USERNAME").build();
+//!   let results = client.search_tracks("antenna", Some("comete")).await?;
+//!   println!("Total results: {}", results.total);
+//!
+//!   let results = results.into_stream().take(10);
+//!   pin_mut!(results);
+//!   while let Some(track) = results.next().await {
+//!     let track = track?;
+//!     println!("{} - {}", track.artist, track.name);
+//!   }
+//!
+//!   Ok(())
+//! }
+//! ```
+use crate::{
+    artist::Artist,
+    errors::Error,
+    imageset::ImageSet,
+    pagination::{PageFetcher, PageRequest},
+    request::Params,
+    serde_helpers::{empty_string_as_none, number_from_any},
+    Client,
+};
+use serde::{Deserialize, Serialize};
+
+/// An artist matching a search (`artist.search`).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArtistMatch {
+    #[serde(flatten)]
+    pub artist: Artist,
+    #[serde(default, deserialize_with = "number_from_any")]
+    pub listeners: u64,
+}
+
+/// An album matching a search (`album.search`).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlbumMatch {
+    pub name: String,
+    /// The name of the artist.
+    pub artist: String,
+    pub url: String,
+    /// The MusicBrainz ID of the album (if known).
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub mbid: Option<String>,
+    #[serde(default)]
+    pub image: ImageSet,
+}
+
+/// A track matching a search (`track.search`).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TrackMatch {
+    pub name: String,
+    /// The name of the artist.
+    pub artist: String,
+    pub url: String,
+    /// The MusicBrainz ID of the track (if known).
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub mbid: Option<String>,
+    #[serde(default)]
+    pub image: ImageSet,
+    #[serde(default, deserialize_with = "number_from_any")]
+    pub listeners: u64,
+}
+
+/// Structs that can be used to get a stream of [`ArtistMatch`]es
+/// (`total` is the total number of results).
+pub type ArtistSearchFetcher = PageFetcher<ArtistMatch>;
+
+/// Structs that can be used to get a stream of [`AlbumMatch`]es
+/// (`total` is the total number of results).
+pub type AlbumSearchFetcher = PageFetcher<AlbumMatch>;
+
+/// Structs that can be used to get a stream of [`TrackMatch`]es
+/// (`total` is the total number of results).
+pub type TrackSearchFetcher = PageFetcher<TrackMatch>;
+
+impl<A: AsRef<str>, U: AsRef<str>> Client<A, U> {
+    /// Creates a new [`ArtistSearchFetcher`] that can be used to fetch the artists matching the given name
+    /// (`artist.search`), best match first.
+    pub async fn search_artists(&self, artist: &str) -> Result<ArtistSearchFetcher, Error> {
+        PageFetcher::new(
+            self.transport(),
+            PageRequest {
+                method: "artist.search",
+                params: Params::new().with("artist", artist).with("limit", 50),
+                root: "results",
+                item: "artistmatches.artist",
+            },
+        )
+        .await
+    }
+
+    /// Creates a new [`AlbumSearchFetcher`] that can be used to fetch the albums matching the given name
+    /// (`album.search`), best match first.
+    pub async fn search_albums(&self, album: &str) -> Result<AlbumSearchFetcher, Error> {
+        PageFetcher::new(
+            self.transport(),
+            PageRequest {
+                method: "album.search",
+                params: Params::new().with("album", album).with("limit", 50),
+                root: "results",
+                item: "albummatches.album",
+            },
+        )
+        .await
+    }
+
+    /// Creates a new [`TrackSearchFetcher`] that can be used to fetch the tracks matching the given name
+    /// (`track.search`), best match first.
+    ///
+    /// The results can be narrowed down to a given artist.
+    pub async fn search_tracks(
+        &self,
+        track: &str,
+        artist: Option<&str>,
+    ) -> Result<TrackSearchFetcher, Error> {
+        PageFetcher::new(
+            self.transport(),
+            PageRequest {
+                method: "track.search",
+                params: Params::new()
+                    .with("track", track)
+                    .with_opt("artist", artist)
+                    .with("limit", 50),
+                root: "results",
+                item: "trackmatches.track",
+            },
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serde_helpers::items_under;
+    use serde_json::json;
+
+    #[test]
+    fn it_deserializes_track_matches() {
+        let raw = json!({
+          "results": {
+            "opensearch:Query": { "#text": "", "role": "request", "startPage": "1" },
+            "opensearch:totalResults": "2",
+            "opensearch:startIndex": "0",
+            "opensearch:itemsPerPage": "50",
+            "trackmatches": {
+              "track": [
+                {
+                  "name": "Antenna",
+                  "artist": "Comete",
+                  "url": "https://www.last.fm/music/Comete/_/Antenna",
+                  "streamable": "0",
+                  "listeners": "54213",
+                  "image": [
+                    { "#text": "https://lastfm.freetls.fastly.net/i/u/34s/2a96cbd8b46e442fc41c2b86b821562f.png", "size": "small" }
+                  ],
+                  "mbid": ""
+                },
+                {
+                  "name": "Antenna",
+                  "artist": "Dirty Three",
+                  "url": "https://www.last.fm/music/Dirty+Three/_/Antenna",
+                  "streamable": "0",
+                  "listeners": "1203",
+                  "image": [],
+                  "mbid": "1b1f6a0a-5dd1-4c4e-8e26-5ec4a3b2e5c7"
+                }
+              ]
+            },
+            "@attr": {}
+          }
+        });
+
+        let tracks: Vec<TrackMatch> = items_under(&raw, "results", "trackmatches.track").unwrap();
+        insta::assert_debug_snapshot!(tracks);
+    }
+
+    #[test]
+    fn it_deserializes_artist_matches() {
+        let raw = json!({
+          "results": {
+            "artistmatches": {
+              "artist": {
+                "name": "Comete",
+                "listeners": "151742",
+                "mbid": "",
+                "url": "https://www.last.fm/music/Comete",
+                "streamable": "0",
+                "image": []
+              }
+            }
+          }
+        });
+
+        let artists: Vec<ArtistMatch> =
+            items_under(&raw, "results", "artistmatches.artist").unwrap();
+        assert_eq!(artists[0].artist.name, "Comete");
+        assert_eq!(artists[0].listeners, 151742);
+    }
+}
@@ -0,0 +1,156 @@
+//! # Serde helpers
+//!
+//! Small deserialization helpers for the loosely typed values returned by the Last.fm API
+//! (e.g. numbers and booleans encoded as strings).
+use crate::lfm_date::LfmDate;
+use chrono::{DateTime, LocalResult, TimeZone, Utc};
+use serde::{de::DeserializeOwned, de::Error, Deserialize, Deserializer};
+use serde_json::Value;
+use std::{fmt::Display, str::FromStr};
+
+/// Deserializes a boolean that can be encoded as `true`/`false`, `0`/`1` or `"0"`/`"1"`.
+pub(crate) fn bool_from_any<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw: Value = Deserialize::deserialize(deserializer)?;
+    match &raw {
+        Value::Bool(b) => Ok(*b),
+        Value::Number(n) => Ok(n.as_u64() == Some(1)),
+        Value::String(s) => match s.as_str() {
+            "1" | "true" => Ok(true),
+            "0" | "false" | "" => Ok(false),
+            _ => Err(D::Error::custom(format!("Cannot parse {s} as a boolean"))),
+        },
+        _ => Err(D::Error::custom(format!("Cannot parse {raw} as a boolean"))),
+    }
+}
+
+/// Deserializes a number that can be encoded either as a JSON number or as a string.
+pub(crate) fn number_from_any<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let raw: Value = Deserialize::deserialize(deserializer)?;
+    match &raw {
+        Value::Number(n) => n.to_string().parse::<T>().map_err(D::Error::custom),
+        Value::String(s) => s.parse::<T>().map_err(D::Error::custom),
+        _ => Err(D::Error::custom(format!("Cannot parse {raw} as a number"))),
+    }
+}
+
+/// Deserializes a list that Last.fm encodes as a single object when it contains only one item.
+pub(crate) fn one_or_many<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        Many(Vec<T>),
+        One(T),
+    }
+
+    match Deserialize::deserialize(deserializer)? {
+        OneOrMany::Many(items) => Ok(items),
+        OneOrMany::One(item) => Ok(vec![item]),
+    }
+}
+
+/// Deserializes a Unix timestamp (in seconds, encoded as a number or a string) into a UTC date.
+pub(crate) fn datetime_from_uts<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let uts: i64 = number_from_any(deserializer)?;
+    match Utc.timestamp_opt(uts, 0) {
+        LocalResult::Single(date) => Ok(date),
+        _ => Err(D::Error::custom("Failed to parse uts as i64")),
+    }
+}
+
+/// Deserializes a Last.fm date object (`{ "uts": "...", "#text": "..." }`) into a UTC date.
+pub(crate) fn datetime_from_lfm_date<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let date: LfmDate = Deserialize::deserialize(deserializer)?;
+    Ok(*date)
+}
+
+/// Deserializes an optional string, normalising empty strings (and `null`) to `None`.
+pub(crate) fn empty_string_as_none<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw: Option<String> = Deserialize::deserialize(deserializer)?;
+    Ok(raw.filter(|s| !s.is_empty()))
+}
+
+/// Deserializes the rank of an item of a chart, from its `@attr` object (e.g. `{ "rank": "1" }`).
+pub(crate) fn rank_from_attr<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Attr {
+        #[serde(deserialize_with = "number_from_any")]
+        rank: u32,
+    }
+
+    let attr: Attr = Deserialize::deserialize(deserializer)?;
+    Ok(attr.rank)
+}
+
+/// A list of items that Last.fm might encode as a single object.
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "T: Deserialize<'de>"))]
+struct Items<T>(#[serde(deserialize_with = "one_or_many")] Vec<T>);
+
+/// Extracts the list of items stored in `raw[root][item]`
+/// (e.g. the artists of `{ "weeklyartistchart": { "artist": [...] } }`).
+///
+/// `item` can be a dot separated path for items nested more deeply (e.g. `artists.artist`).
+/// A missing item is treated as an empty list.
+pub(crate) fn items_under<T: DeserializeOwned>(
+    raw: &Value,
+    root: &str,
+    item: &str,
+) -> Result<Vec<T>, serde_json::Error> {
+    let raw_root = raw
+        .get(root)
+        .ok_or_else(|| serde_json::Error::custom(format!("missing field `{root}`")))?;
+
+    let raw_items = item
+        .split('.')
+        .try_fold(raw_root, |value, key| value.get(key));
+
+    match raw_items {
+        Some(raw_items) => Ok(serde_json::from_value::<Items<T>>(raw_items.clone())?.0),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Deserializes an optional number that can be encoded either as a JSON number or as a string.
+pub(crate) fn option_number_from_any<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let raw: Option<Value> = Deserialize::deserialize(deserializer)?;
+    match raw {
+        Some(Value::Number(n)) => n
+            .to_string()
+            .parse::<T>()
+            .map(Some)
+            .map_err(D::Error::custom),
+        Some(Value::String(s)) if s.is_empty() => Ok(None),
+        Some(Value::String(s)) => s.parse::<T>().map(Some).map_err(D::Error::custom),
+        None | Some(Value::Null) => Ok(None),
+        Some(raw) => Err(D::Error::custom(format!("Cannot parse {raw} as a number"))),
+    }
+}
@@ -0,0 +1,58 @@
+//! # Tag
+//!
+//! defines the [`Tag`] struct and its methods.
+use crate::serde_helpers::{number_from_any, one_or_many};
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+/// A Last.fm tag.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Tag {
+    pub name: String,
+    #[serde(default)]
+    pub url: String,
+    /// How many times the tag was used in the given context
+    /// (e.g. by the user for [`Client::top_tags`](crate::Client::top_tags)), `0` if not available.
+    #[serde(default, deserialize_with = "number_from_any")]
+    pub count: u64,
+}
+
+/// Deserializes the tags attached to an artist, album or track (`{ "tag": [...] }`).
+///
+/// Last.fm returns an empty string (rather than an empty object) when there are no tags.
+pub(crate) fn tags_from_any<'de, D>(deserializer: D) -> Result<Vec<Tag>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct RawTags {
+        #[serde(default, deserialize_with = "one_or_many")]
+        tag: Vec<Tag>,
+    }
+
+    let raw: Value = Deserialize::deserialize(deserializer)?;
+    match raw {
+        Value::String(_) | Value::Null => Ok(Vec::new()),
+        raw => Ok(RawTags::deserialize(raw).map_err(D::Error::custom)?.tag),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn it_deserializes_correctly() {
+        let json_value = json!({
+          "name": "rock",
+          "count": "12",
+          "url": "https://www.last.fm/tag/rock"
+        });
+
+        let tag: Tag = serde_json::from_value(json_value).unwrap();
+        assert_eq!(tag.name, "rock");
+        assert_eq!(tag.url, "https://www.last.fm/tag/rock");
+        assert_eq!(tag.count, 12);
+    }
+}
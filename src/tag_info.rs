@@ -0,0 +1,199 @@
+//! # Tag info
+//!
+//! Defines the [`TagInfo`] struct and the methods to browse the artists, albums and tracks of a tag
+//! (`tag.*`).
+//!
+//! ```rust,no_run
+//! use futures_util::pin_mut;
+//! use futures_util::stream::StreamExt;
+//! # use lastfm::Client;
+//! #
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!   let client = Client::builder().api_key("YOUR_API_KEY").username("YOUR_USERNAME").build();
+//!   let info = client.tag_info("shoegaze").await?;
+//!   println!("{}: used {} times by {} users", info.name, info.taggings, info.reach);
+//!
+//!   let top_artists = client.tag_top_artists("shoegaze").await?.into_stream();
+//!   pin_mut!(top_artists);
+//!   while let Some(artist) = top_artists.next().await {
+//!     let artist = artist?;
+//!     println!("{}. {}", artist.rank, artist.artist.name);
+//!   }
+//!
+//!   Ok(())
+//! }
+//! ```
+use crate::{
+    errors::Error,
+    pagination::{PageFetcher, PageRequest},
+    request::{Auth, Params},
+    serde_helpers::{items_under, number_from_any},
+    tag::Tag,
+    top::{TopAlbum, TopArtist, TopTrack},
+    weekly_charts::ChartRange,
+    wiki::Wiki,
+    Client,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The metadata of a tag (`tag.getInfo`).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TagInfo {
+    pub name: String,
+    /// The number of users who used the tag.
+    #[serde(deserialize_with = "number_from_any")]
+    pub reach: u64,
+    /// The number of times the tag was used.
+    #[serde(
+        rename(deserialize = "total"),
+        alias = "taggings",
+        deserialize_with = "number_from_any"
+    )]
+    pub taggings: u64,
+    #[serde(default)]
+    pub wiki: Option<Wiki>,
+}
+
+#[derive(Deserialize)]
+struct TagInfoResponse {
+    tag: TagInfo,
+}
+
+/// Structs that can be used to get a stream of the [`TopArtist`]s of a tag.
+pub type TagTopArtistsFetcher = PageFetcher<TopArtist>;
+
+/// Structs that can be used to get a stream of the [`TopAlbum`]s of a tag.
+pub type TagTopAlbumsFetcher = PageFetcher<TopAlbum>;
+
+/// Structs that can be used to get a stream of the [`TopTrack`]s of a tag.
+pub type TagTopTracksFetcher = PageFetcher<TopTrack>;
+
+impl<A: AsRef<str>, U: AsRef<str>> Client<A, U> {
+    /// Fetches the metadata of a tag (`tag.getInfo`).
+    pub async fn tag_info(&self, tag: &str) -> Result<TagInfo, Error> {
+        let params = Params::new().with("tag", tag);
+        let res: TagInfoResponse = self
+            .transport()
+            .get("tag.getInfo", params, Auth::None)
+            .await?;
+
+        Ok(res.tag)
+    }
+
+    /// Fetches the tags similar to the given one (`tag.getSimilar`).
+    pub async fn similar_tags(&self, tag: &str) -> Result<Vec<Tag>, Error> {
+        let params = Params::new().with("tag", tag);
+        let raw: Value = self
+            .transport()
+            .get("tag.getSimilar", params, Auth::None)
+            .await?;
+
+        Ok(items_under(&raw, "similartags", "tag")?)
+    }
+
+    /// Builds the request for one of the `tag.getTop*` methods.
+    fn tag_top_request(
+        &self,
+        method: &'static str,
+        root: &'static str,
+        item: &'static str,
+        tag: &str,
+    ) -> PageRequest {
+        PageRequest {
+            method,
+            params: Params::new().with("tag", tag).with("limit", 200),
+            root,
+            item,
+        }
+    }
+
+    /// Creates a new [`TagTopArtistsFetcher`] that can be used to fetch the top artists of a tag
+    /// (`tag.getTopArtists`).
+    pub async fn tag_top_artists(&self, tag: &str) -> Result<TagTopArtistsFetcher, Error> {
+        let request = self.tag_top_request("tag.getTopArtists", "topartists", "artist", tag);
+        PageFetcher::new(self.transport(), request).await
+    }
+
+    /// Creates a new [`TagTopAlbumsFetcher`] that can be used to fetch the top albums of a tag
+    /// (`tag.getTopAlbums`).
+    pub async fn tag_top_albums(&self, tag: &str) -> Result<TagTopAlbumsFetcher, Error> {
+        let request = self.tag_top_request("tag.getTopAlbums", "albums", "album", tag);
+        PageFetcher::new(self.transport(), request).await
+    }
+
+    /// Creates a new [`TagTopTracksFetcher`] that can be used to fetch the top tracks of a tag
+    /// (`tag.getTopTracks`).
+    pub async fn tag_top_tracks(&self, tag: &str) -> Result<TagTopTracksFetcher, Error> {
+        let request = self.tag_top_request("tag.getTopTracks", "tracks", "track", tag);
+        PageFetcher::new(self.transport(), request).await
+    }
+
+    /// Fetches the list of ranges for which the weekly charts of a tag are available
+    /// (`tag.getWeeklyChartList`), from the oldest to the most recent.
+    pub async fn tag_weekly_chart_list(&self, tag: &str) -> Result<Vec<ChartRange>, Error> {
+        let params = Params::new().with("tag", tag);
+        let raw: Value = self
+            .transport()
+            .get("tag.getWeeklyChartList", params, Auth::None)
+            .await?;
+
+        Ok(items_under(&raw, "weeklychartlist", "chart")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn it_deserializes_tag_info() {
+        let json_value = json!({
+          "tag": {
+            "name": "shoegaze",
+            "total": 512377,
+            "reach": 61245,
+            "wiki": {
+              "summary": "Shoegaze is a subgenre of indie and alternative rock.",
+              "content": "Shoegaze is a subgenre of indie and alternative rock that emerged in the late 1980s."
+            }
+          }
+        });
+
+        let res: TagInfoResponse = serde_json::from_value(json_value).unwrap();
+        insta::assert_debug_snapshot!(res.tag);
+    }
+
+    #[test]
+    fn it_deserializes_tag_top_tracks() {
+        let raw = json!({
+          "tracks": {
+            "track": [
+              {
+                "name": "Only Shallow",
+                "duration": "257",
+                "mbid": "",
+                "url": "https://www.last.fm/music/My+Bloody+Valentine/_/Only+Shallow",
+                "streamable": { "#text": "0", "fulltrack": "0" },
+                "artist": {
+                  "name": "My Bloody Valentine",
+                  "mbid": "8a6ae1c5-a6a1-4a5e-8a1f-9c5c0b07f5e1",
+                  "url": "https://www.last.fm/music/My+Bloody+Valentine"
+                },
+                "image": [],
+                "@attr": { "rank": "1" }
+              }
+            ],
+            "@attr": { "tag": "shoegaze", "page": "1", "perPage": "50", "totalPages": "20", "total": "1000" }
+          }
+        });
+
+        let tracks: Vec<TopTrack> = items_under(&raw, "tracks", "track").unwrap();
+        assert_eq!(tracks[0].rank, 1);
+        assert_eq!(tracks[0].duration, 257);
+        assert_eq!(tracks[0].playcount, 0);
+        assert_eq!(tracks[0].artist.name, "My Bloody Valentine");
+    }
+}
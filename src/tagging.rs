@@ -0,0 +1,244 @@
+//! # Tagging
+//!
+//! Defines the methods to add and remove the authenticated user's tags on artists, albums and tracks,
+//! and to list them (`artist.addTags`, `artist.removeTag`, `artist.getTags` and their album and track
+//! counterparts).
+//!
+//! All these methods require the client to be configured with an `api_secret` and a `session_key`.
+//!
+//! ```rust,no_run
+//! # use lastfm::{artist_info::ArtistQuery, Client};
+//! #
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!   let client = Client::<String, &str>::from_env("YOUR_USERNAME");
+//!   client.add_artist_tags("Comete", &["indie", "italian"]).await?;
+//!   client.remove_artist_tag("Comete", "italian").await?;
+//!
+//!   for tag in client.artist_tags(&ArtistQuery::by_name("Comete")).await? {
+//!     println!("{}", tag.name);
+//!   }
+//!
+//!   Ok(())
+//! }
+//! ```
+use crate::{
+    album_info::AlbumQuery,
+    artist_info::ArtistQuery,
+    errors::Error,
+    request::{Auth, Params},
+    serde_helpers::items_under,
+    tag::Tag,
+    track_info::TrackQuery,
+    Client,
+};
+use serde_json::Value;
+
+/// The maximum number of tags that can be sent in a single `*.addTags` request.
+pub const MAX_TAGS_PER_REQUEST: usize = 10;
+
+/// Rejects blank tags and tags containing a comma (which Last.fm would split into several tags).
+fn validate_tag(tag: &str) -> Result<(), Error> {
+    if tag.trim().is_empty() || tag.contains(',') {
+        return Err(Error::InvalidTag(tag.to_string()));
+    }
+    Ok(())
+}
+
+/// Validates the given tags and joins them in the comma separated list expected by Last.fm.
+fn tags_param<T: AsRef<str>>(tags: &[T]) -> Result<String, Error> {
+    if tags.len() > MAX_TAGS_PER_REQUEST {
+        return Err(Error::TooManyTags(tags.len()));
+    }
+    for tag in tags {
+        validate_tag(tag.as_ref())?;
+    }
+
+    Ok(tags
+        .iter()
+        .map(|tag| tag.as_ref())
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+impl<A: AsRef<str>, U: AsRef<str>> Client<A, U> {
+    /// Sends one of the `*.addTags` requests, validating the tags first.
+    ///
+    /// An empty list of tags is a no-op: no request is sent.
+    async fn add_tags<T: AsRef<str>>(
+        &self,
+        method: &str,
+        params: Params,
+        tags: &[T],
+    ) -> Result<(), Error> {
+        if tags.is_empty() {
+            return Ok(());
+        }
+        let params = params.with("tags", tags_param(tags)?);
+
+        self.transport()
+            .post::<Value>(method, params, Auth::Session)
+            .await?;
+        Ok(())
+    }
+
+    /// Sends one of the `*.removeTag` requests, validating the tag first.
+    async fn remove_tag(&self, method: &str, params: Params, tag: &str) -> Result<(), Error> {
+        validate_tag(tag)?;
+        let params = params.with("tag", tag);
+        self.transport()
+            .post::<Value>(method, params, Auth::Session)
+            .await?;
+        Ok(())
+    }
+
+    /// Fetches the tags applied by the authenticated user with one of the `*.getTags` requests.
+    async fn user_tags(&self, method: &str, params: Params) -> Result<Vec<Tag>, Error> {
+        let raw: Value = self.transport().get(method, params, Auth::Session).await?;
+        Ok(items_under(&raw, "tags", "tag")?)
+    }
+
+    /// Tags an artist for the authenticated user (`artist.addTags`).
+    ///
+    /// At most [`MAX_TAGS_PER_REQUEST`] tags can be added at once, otherwise [`Error::TooManyTags`]
+    /// is returned without sending the request. Blank tags and tags containing a comma are rejected
+    /// with [`Error::InvalidTag`]. An empty list of tags is a no-op.
+    pub async fn add_artist_tags<T: AsRef<str>>(
+        &self,
+        artist: &str,
+        tags: &[T],
+    ) -> Result<(), Error> {
+        let params = Params::new().with("artist", artist);
+        self.add_tags("artist.addTags", params, tags).await
+    }
+
+    /// Removes a tag of the authenticated user from an artist (`artist.removeTag`).
+    ///
+    /// Blank tags and tags containing a comma are rejected with [`Error::InvalidTag`].
+    pub async fn remove_artist_tag(&self, artist: &str, tag: &str) -> Result<(), Error> {
+        let params = Params::new().with("artist", artist);
+        self.remove_tag("artist.removeTag", params, tag).await
+    }
+
+    /// Fetches the tags the authenticated user applied to an artist (`artist.getTags`).
+    pub async fn artist_tags(&self, query: &ArtistQuery) -> Result<Vec<Tag>, Error> {
+        self.user_tags("artist.getTags", query.params()).await
+    }
+
+    /// Tags an album for the authenticated user (`album.addTags`).
+    ///
+    /// At most [`MAX_TAGS_PER_REQUEST`] tags can be added at once, otherwise [`Error::TooManyTags`]
+    /// is returned without sending the request. Blank tags and tags containing a comma are rejected
+    /// with [`Error::InvalidTag`]. An empty list of tags is a no-op.
+    pub async fn add_album_tags<T: AsRef<str>>(
+        &self,
+        artist: &str,
+        album: &str,
+        tags: &[T],
+    ) -> Result<(), Error> {
+        let params = Params::new().with("artist", artist).with("album", album);
+        self.add_tags("album.addTags", params, tags).await
+    }
+
+    /// Removes a tag of the authenticated user from an album (`album.removeTag`).
+    ///
+    /// Blank tags and tags containing a comma are rejected with [`Error::InvalidTag`].
+    pub async fn remove_album_tag(
+        &self,
+        artist: &str,
+        album: &str,
+        tag: &str,
+    ) -> Result<(), Error> {
+        let params = Params::new().with("artist", artist).with("album", album);
+        self.remove_tag("album.removeTag", params, tag).await
+    }
+
+    /// Fetches the tags the authenticated user applied to an album (`album.getTags`).
+    pub async fn album_tags(&self, query: &AlbumQuery) -> Result<Vec<Tag>, Error> {
+        self.user_tags("album.getTags", query.params()).await
+    }
+
+    /// Tags a track for the authenticated user (`track.addTags`).
+    ///
+    /// At most [`MAX_TAGS_PER_REQUEST`] tags can be added at once, otherwise [`Error::TooManyTags`]
+    /// is returned without sending the request. Blank tags and tags containing a comma are rejected
+    /// with [`Error::InvalidTag`]. An empty list of tags is a no-op.
+    pub async fn add_track_tags<T: AsRef<str>>(
+        &self,
+        artist: &str,
+        track: &str,
+        tags: &[T],
+    ) -> Result<(), Error> {
+        let params = Params::new().with("artist", artist).with("track", track);
+        self.add_tags("track.addTags", params, tags).await
+    }
+
+    /// Removes a tag of the authenticated user from a track (`track.removeTag`).
+    ///
+    /// Blank tags and tags containing a comma are rejected with [`Error::InvalidTag`].
+    pub async fn remove_track_tag(
+        &self,
+        artist: &str,
+        track: &str,
+        tag: &str,
+    ) -> Result<(), Error> {
+        let params = Params::new().with("artist", artist).with("track", track);
+        self.remove_tag("track.removeTag", params, tag).await
+    }
+
+    /// Fetches the tags the authenticated user applied to a track (`track.getTags`).
+    pub async fn track_tags(&self, query: &TrackQuery) -> Result<Vec<Tag>, Error> {
+        self.user_tags("track.getTags", query.params()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn it_joins_tags() {
+        assert_eq!(tags_param(&["indie", "italian"]).unwrap(), "indie,italian");
+        assert_eq!(tags_param::<&str>(&[]).unwrap(), "");
+    }
+
+    #[test]
+    fn it_rejects_too_many_tags() {
+        let tags: Vec<String> = (0..=MAX_TAGS_PER_REQUEST)
+            .map(|i| format!("tag{i}"))
+            .collect();
+        assert!(matches!(tags_param(&tags), Err(Error::TooManyTags(11))));
+        assert!(tags_param(&tags[..MAX_TAGS_PER_REQUEST]).is_ok());
+    }
+
+    #[test]
+    fn it_rejects_invalid_tags() {
+        assert!(matches!(
+            tags_param(&["indie", "rock,pop"]),
+            Err(Error::InvalidTag(tag)) if tag == "rock,pop"
+        ));
+        assert!(matches!(
+            tags_param(&["indie", "  "]),
+            Err(Error::InvalidTag(_))
+        ));
+        assert!(matches!(tags_param(&[""]), Err(Error::InvalidTag(_))));
+    }
+
+    #[test]
+    fn it_validates_a_single_tag() {
+        assert!(validate_tag("indie").is_ok());
+        assert!(matches!(
+            validate_tag("rock,pop"),
+            Err(Error::InvalidTag(tag)) if tag == "rock,pop"
+        ));
+        assert!(matches!(validate_tag(" "), Err(Error::InvalidTag(_))));
+    }
+
+    #[test]
+    fn it_deserializes_empty_user_tags() {
+        let raw = json!({ "tags": { "#text": "\n", "artist": "Comete" } });
+        let tags: Vec<Tag> = items_under(&raw, "tags", "tag").unwrap();
+        assert!(tags.is_empty());
+    }
+}
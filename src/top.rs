@@ -0,0 +1,242 @@
+//! # Top charts
+//!
+//! Defines the [`TopArtist`], [`TopAlbum`] and [`TopTrack`] structs and the methods to fetch
+//! the user's top artists, albums and tracks for a given [`Period`].
+//!
+//! ```rust,no_run
+//! use futures_util::pin_mut;
+//! use futures_util::stream::StreamExt;
+//! # use lastfm::{top::Period, Client};
+//! #
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!   let client = Client::builder().api_key("YOUR_API_KEY").username("YOUR_USERNAME").build();
+//!   let top_artists = client.top_artists(Period::SevenDays).await?.into_stream();
+//!   pin_mut!(top_artists);
+//!   while let Some(artist) = top_artists.next().await {
+//!     let artist = artist?;
+//!     println!("{}. {} ({} plays)", artist.rank, artist.artist.name, artist.playcount);
+//!   }
+//!
+//!   Ok(())
+//! }
+//! ```
+use crate::{
+    artist::Artist,
+    errors::Error,
+    imageset::ImageSet,
+    pagination::{PageFetcher, PageRequest},
+    request::Params,
+    serde_helpers::{empty_string_as_none, number_from_any, rank_from_attr},
+    Client,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// The time period covered by a chart.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Period {
+    /// All time.
+    #[default]
+    #[serde(rename = "overall")]
+    Overall,
+    /// The last 7 days.
+    #[serde(rename = "7day")]
+    SevenDays,
+    /// The last month.
+    #[serde(rename = "1month")]
+    OneMonth,
+    /// The last 3 months.
+    #[serde(rename = "3month")]
+    ThreeMonths,
+    /// The last 6 months.
+    #[serde(rename = "6month")]
+    SixMonths,
+    /// The last 12 months.
+    #[serde(rename = "12month")]
+    TwelveMonths,
+}
+
+impl Display for Period {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Period::Overall => "overall",
+            Period::SevenDays => "7day",
+            Period::OneMonth => "1month",
+            Period::ThreeMonths => "3month",
+            Period::SixMonths => "6month",
+            Period::TwelveMonths => "12month",
+        };
+        write!(f, "{value}")
+    }
+}
+
+/// An artist in a top artists chart.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TopArtist {
+    #[serde(flatten)]
+    pub artist: Artist,
+    /// The position of the artist in the chart (starting from 1), `0` if Last.fm does not rank
+    /// the artists (e.g. [`Client::chart_top_artists`]).
+    #[serde(
+        default,
+        rename(deserialize = "@attr"),
+        deserialize_with = "rank_from_attr"
+    )]
+    pub rank: u32,
+    /// The number of plays (`0` if not available, e.g. in the top charts of a tag).
+    #[serde(default, deserialize_with = "number_from_any")]
+    pub playcount: u64,
+}
+
+/// An album in a top albums chart.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TopAlbum {
+    pub name: String,
+    pub artist: Artist,
+    pub url: String,
+    /// The MusicBrainz ID of the album (if known).
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub mbid: Option<String>,
+    #[serde(default)]
+    pub image: ImageSet,
+    /// The position of the album in the chart (starting from 1), `0` if Last.fm does not rank
+    /// the albums (e.g. [`Client::artist_top_albums`]).
+    #[serde(
+        default,
+        rename(deserialize = "@attr"),
+        deserialize_with = "rank_from_attr"
+    )]
+    pub rank: u32,
+    /// The number of plays (`0` if not available, e.g. in the top charts of a tag).
+    #[serde(default, deserialize_with = "number_from_any")]
+    pub playcount: u64,
+}
+
+/// A track in a top tracks chart.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TopTrack {
+    pub name: String,
+    pub artist: Artist,
+    pub url: String,
+    /// The MusicBrainz ID of the track (if known).
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub mbid: Option<String>,
+    #[serde(default)]
+    pub image: ImageSet,
+    /// The length of the track in seconds (`0` if unknown).
+    #[serde(default, deserialize_with = "number_from_any")]
+    pub duration: u32,
+    /// The position of the track in the chart (starting from 1), `0` if Last.fm does not rank
+    /// the tracks (e.g. [`Client::chart_top_tracks`]).
+    #[serde(
+        default,
+        rename(deserialize = "@attr"),
+        deserialize_with = "rank_from_attr"
+    )]
+    pub rank: u32,
+    /// The number of plays (`0` if not available, e.g. in the top charts of a tag).
+    #[serde(default, deserialize_with = "number_from_any")]
+    pub playcount: u64,
+}
+
+/// Structs that can be used to get a stream of [`TopArtist`]s.
+pub type TopArtistsFetcher = PageFetcher<TopArtist>;
+
+/// Structs that can be used to get a stream of [`TopAlbum`]s.
+pub type TopAlbumsFetcher = PageFetcher<TopAlbum>;
+
+/// Structs that can be used to get a stream of [`TopTrack`]s.
+pub type TopTracksFetcher = PageFetcher<TopTrack>;
+
+impl<A: AsRef<str>, U: AsRef<str>> Client<A, U> {
+    /// Builds the request for one of the `user.getTop*` methods.
+    fn top_request(
+        &self,
+        method: &'static str,
+        root: &'static str,
+        item: &'static str,
+        period: Period,
+    ) -> PageRequest {
+        PageRequest {
+            method,
+            params: Params::new()
+                .with("user", self.username())
+                .with("period", period)
+                .with("limit", 200),
+            root,
+            item,
+        }
+    }
+
+    /// Creates a new [`TopArtistsFetcher`] that can be used to fetch the user's top artists in the given period.
+    pub async fn top_artists(&self, period: Period) -> Result<TopArtistsFetcher, Error> {
+        let request = self.top_request("user.getTopArtists", "topartists", "artist", period);
+        PageFetcher::new(self.transport(), request).await
+    }
+
+    /// Creates a new [`TopAlbumsFetcher`] that can be used to fetch the user's top albums in the given period.
+    pub async fn top_albums(&self, period: Period) -> Result<TopAlbumsFetcher, Error> {
+        let request = self.top_request("user.getTopAlbums", "topalbums", "album", period);
+        PageFetcher::new(self.transport(), request).await
+    }
+
+    /// Creates a new [`TopTracksFetcher`] that can be used to fetch the user's top tracks in the given period.
+    pub async fn top_tracks(&self, period: Period) -> Result<TopTracksFetcher, Error> {
+        let request = self.top_request("user.getTopTracks", "toptracks", "track", period);
+        PageFetcher::new(self.transport(), request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn it_formats_periods() {
+        assert_eq!(Period::Overall.to_string(), "overall");
+        assert_eq!(Period::SevenDays.to_string(), "7day");
+        assert_eq!(Period::TwelveMonths.to_string(), "12month");
+    }
+
+    #[test]
+    fn it_deserializes_a_top_artist() {
+        let json_value = json!({
+          "streamable": "0",
+          "image": [],
+          "mbid": "",
+          "url": "https://www.last.fm/music/Comete",
+          "playcount": "1234",
+          "@attr": { "rank": "1" },
+          "name": "Comete"
+        });
+
+        let artist: TopArtist = serde_json::from_value(json_value).unwrap();
+        assert_eq!(artist.artist.name, "Comete");
+        assert_eq!(artist.rank, 1);
+        assert_eq!(artist.playcount, 1234);
+    }
+
+    #[test]
+    fn it_deserializes_a_top_track() {
+        let json_value = json!({
+          "streamable": { "fulltrack": "0", "#text": "0" },
+          "mbid": "",
+          "name": "Antenna",
+          "image": [],
+          "artist": {
+            "url": "https://www.last.fm/music/Comete",
+            "name": "Comete",
+            "mbid": ""
+          },
+          "url": "https://www.last.fm/music/Comete/_/Antenna",
+          "duration": "215",
+          "@attr": { "rank": "2" },
+          "playcount": "42"
+        });
+
+        let track: TopTrack = serde_json::from_value(json_value).unwrap();
+        insta::assert_debug_snapshot!(track);
+    }
+}
@@ -1,8 +1,10 @@
 //! # Tracks
 //!
 //! Defines the [`Track`] struct and its methods.
+use crate::album::Album;
 use crate::artist::Artist;
 use crate::imageset::ImageSet;
+use crate::serde_helpers::{bool_from_any, empty_string_as_none};
 use chrono::{DateTime, LocalResult, TimeZone, Utc};
 use serde::{de::Error, Deserialize, Deserializer, Serialize};
 use serde_json::{Map, Value};
@@ -20,8 +22,14 @@ pub struct NowPlayingTrack {
     pub artist: Artist,
     pub name: String,
     pub image: ImageSet,
-    pub album: String,
+    pub album: Album,
     pub url: String,
+    /// The MusicBrainz ID of the track (if known).
+    pub mbid: Option<String>,
+    /// Whether the user loved the track.
+    pub loved: bool,
+    /// Whether the track can be streamed from Last.fm.
+    pub streamable: bool,
 }
 
 /// A Last.fm track that has been recorded.
@@ -30,9 +38,15 @@ pub struct RecordedTrack {
     pub artist: Artist,
     pub name: String,
     pub image: ImageSet,
-    pub album: String,
+    pub album: Album,
     pub url: String,
+    /// The MusicBrainz ID of the track (if known).
+    pub mbid: Option<String>,
     pub date: DateTime<Utc>,
+    /// Whether the user loved the track.
+    pub loved: bool,
+    /// Whether the track can be streamed from Last.fm.
+    pub streamable: bool,
 }
 
 fn is_now_playing(value: &Map<String, Value>) -> bool {
@@ -91,15 +105,11 @@ impl<'de> Deserialize<'de> for Track {
             .map_err(|e| D::Error::custom(format!("Cannot deserialize image: {e}")))?;
 
         // deserialize album
-        let album = raw_data
+        let raw_album = raw_data
             .get("album")
-            .ok_or_else(|| D::Error::missing_field("album"))?
-            .as_object()
-            .ok_or_else(|| D::Error::custom("Field album is not an object"))?
-            .get("#text")
-            .ok_or_else(|| D::Error::missing_field("#text"))?
-            .as_str()
-            .ok_or_else(|| D::Error::custom("Field #text is not a string"))?;
+            .ok_or_else(|| D::Error::missing_field("album"))?;
+        let album: Album = serde_json::from_value::<Album>(raw_album.clone())
+            .map_err(|e| D::Error::custom(format!("Cannot deserialize album: {e}")))?;
 
         // deserialize url
         let url = raw_data
@@ -108,13 +118,37 @@ impl<'de> Deserialize<'de> for Track {
             .as_str()
             .ok_or_else(|| D::Error::custom("Field url is not a string"))?;
 
+        // deserialize mbid
+        let mbid = match raw_data.get("mbid") {
+            Some(raw_mbid) => empty_string_as_none(raw_mbid)
+                .map_err(|e| D::Error::custom(format!("Cannot deserialize mbid: {e}")))?,
+            None => None,
+        };
+
+        // deserialize loved (only available in extended mode)
+        let loved = match raw_data.get("loved") {
+            Some(raw_loved) => bool_from_any(raw_loved)
+                .map_err(|e| D::Error::custom(format!("Cannot deserialize loved: {e}")))?,
+            None => false,
+        };
+
+        // deserialize streamable
+        let streamable = match raw_data.get("streamable") {
+            Some(raw_streamable) => bool_from_any(raw_streamable)
+                .map_err(|e| D::Error::custom(format!("Cannot deserialize streamable: {e}")))?,
+            None => false,
+        };
+
         if is_now_playing(raw_data) {
             return Ok(Track::NowPlaying(NowPlayingTrack {
                 artist,
                 name: name.to_string(),
                 image,
-                album: album.to_string(),
+                album,
                 url: url.to_string(),
+                mbid,
+                loved,
+                streamable,
             }));
         }
 
@@ -138,9 +172,12 @@ impl<'de> Deserialize<'de> for Track {
                 artist,
                 name: name.to_string(),
                 image,
-                album: album.to_string(),
+                album,
                 url: url.to_string(),
+                mbid,
                 date,
+                loved,
+                streamable,
             }))
         } else {
             Err(D::Error::custom("Failed to parse uts as i64"))
@@ -166,4 +203,12 @@ mod tests {
         let track: Track = serde_json::from_str(now_playing).unwrap();
         insta::assert_debug_snapshot!(track);
     }
+
+    #[test]
+    fn test_recorded_non_extended() {
+        let recorded = include_str!("fixtures/recorded_track_non_extended.json");
+
+        let track: Track = serde_json::from_str(recorded).unwrap();
+        insta::assert_debug_snapshot!(track);
+    }
 }
@@ -0,0 +1,408 @@
+//! # Track info
+//!
+//! Defines the [`TrackQuery`], [`TrackInfo`], [`SimilarTrack`] and [`TrackCorrection`] structs
+//! and the methods to fetch the metadata of a track (`track.*`).
+//!
+//! ```rust,no_run
+//! # use lastfm::{track_info::TrackQuery, Client};
+//! #
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!   let client = Client::builder().api_key("YOUR_API_KEY").username("YOUR_USERNAME").build();
+//!   if let Some(correction) = client.track_correction("comete", "antena").await? {
+//!     println!("Did you mean {} - {}?", correction.artist.name, correction.name);
+//!   }
+//!
+//!   let query = TrackQuery::by_name("Comete", "Antenna").username("YOUR_USERNAME");
+//!   let info = client.track_info(&query).await?;
+//!   println!("{}: {} listeners, loved: {}", info.name, info.listeners, info.user_loved);
+//!
+//!   Ok(())
+//! }
+//! ```
+use crate::{
+    artist::Artist,
+    errors::Error,
+    imageset::ImageSet,
+    request::{Auth, Params, QueryOptions},
+    serde_helpers::{
+        bool_from_any, empty_string_as_none, items_under, number_from_any, option_number_from_any,
+    },
+    tag::{tags_from_any, Tag},
+    track::RecordedTrack,
+    wiki::Wiki,
+    Client,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// How a track is looked up.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum TrackLookup {
+    Name { artist: String, track: String },
+    Mbid(String),
+}
+
+/// Identifies the track of a `track.*` call, either by artist and track name or by MusicBrainz ID.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TrackQuery {
+    lookup: TrackLookup,
+    options: QueryOptions,
+}
+
+impl TrackQuery {
+    /// Looks up the track by artist and track name.
+    pub fn by_name(artist: impl Into<String>, track: impl Into<String>) -> Self {
+        TrackQuery {
+            lookup: TrackLookup::Name {
+                artist: artist.into(),
+                track: track.into(),
+            },
+            options: QueryOptions::default(),
+        }
+    }
+
+    /// Looks up the track by MusicBrainz ID.
+    pub fn by_mbid(mbid: impl Into<String>) -> Self {
+        TrackQuery {
+            lookup: TrackLookup::Mbid(mbid.into()),
+            options: QueryOptions::default(),
+        }
+    }
+
+    /// Whether Last.fm should correct misspelled artist and track names.
+    pub fn autocorrect(mut self, autocorrect: bool) -> Self {
+        self.options.autocorrect = autocorrect;
+        self
+    }
+
+    /// Includes the statistics of the given user (e.g. [`TrackInfo::user_playcount`]) in the
+    /// `track.getInfo` response.
+    pub fn username(mut self, username: impl Into<String>) -> Self {
+        self.options.username = Some(username.into());
+        self
+    }
+
+    /// The parameters identifying the track.
+    pub(crate) fn params(&self) -> Params {
+        let params = match &self.lookup {
+            TrackLookup::Name { artist, track } => {
+                Params::new().with("artist", artist).with("track", track)
+            }
+            TrackLookup::Mbid(mbid) => Params::new().with("mbid", mbid),
+        };
+        self.options.with_autocorrect(params)
+    }
+
+    /// The parameters of a `track.getInfo` request, including the username (if set).
+    fn info_params(&self) -> Params {
+        self.options.with_username(self.params())
+    }
+}
+
+impl From<&RecordedTrack> for TrackQuery {
+    /// Looks up a scrobbled track by its artist and track name.
+    fn from(track: &RecordedTrack) -> Self {
+        TrackQuery::by_name(&track.artist.name, &track.name)
+    }
+}
+
+/// The album a track belongs to, as returned by `track.getInfo`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TrackAlbum {
+    #[serde(rename(deserialize = "title"))]
+    pub name: String,
+    /// The name of the artist of the album.
+    pub artist: String,
+    pub url: String,
+    /// The MusicBrainz ID of the album (if known).
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub mbid: Option<String>,
+    #[serde(default)]
+    pub image: ImageSet,
+}
+
+/// The metadata of a track (`track.getInfo`).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "RawTrackInfo")]
+pub struct TrackInfo {
+    pub name: String,
+    pub artist: Artist,
+    pub url: String,
+    /// The MusicBrainz ID of the track (if known).
+    pub mbid: Option<String>,
+    /// The length of the track in seconds (`0` if unknown).
+    pub duration: u32,
+    pub listeners: u64,
+    pub playcount: u64,
+    /// How many times the user listened to the track (only if set with [`TrackQuery::username`]).
+    pub user_playcount: Option<u64>,
+    /// Whether the user loved the track (always `false` unless set with [`TrackQuery::username`]).
+    pub user_loved: bool,
+    pub album: Option<TrackAlbum>,
+    /// The top tags of the track.
+    pub tags: Vec<Tag>,
+    pub wiki: Option<Wiki>,
+}
+
+#[derive(Deserialize)]
+struct RawTrackInfo {
+    name: String,
+    artist: Artist,
+    url: String,
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    mbid: Option<String>,
+    /// In milliseconds.
+    #[serde(default, deserialize_with = "number_from_any")]
+    duration: u32,
+    #[serde(deserialize_with = "number_from_any")]
+    listeners: u64,
+    #[serde(deserialize_with = "number_from_any")]
+    playcount: u64,
+    #[serde(default, deserialize_with = "option_number_from_any")]
+    userplaycount: Option<u64>,
+    #[serde(default, deserialize_with = "bool_from_any")]
+    userloved: bool,
+    #[serde(default)]
+    album: Option<TrackAlbum>,
+    #[serde(default, deserialize_with = "tags_from_any")]
+    toptags: Vec<Tag>,
+    #[serde(default)]
+    wiki: Option<Wiki>,
+}
+
+impl From<RawTrackInfo> for TrackInfo {
+    fn from(raw: RawTrackInfo) -> Self {
+        TrackInfo {
+            name: raw.name,
+            artist: raw.artist,
+            url: raw.url,
+            mbid: raw.mbid,
+            duration: raw.duration / 1000,
+            listeners: raw.listeners,
+            playcount: raw.playcount,
+            user_playcount: raw.userplaycount,
+            user_loved: raw.userloved,
+            album: raw.album,
+            tags: raw.toptags,
+            wiki: raw.wiki,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct TrackInfoResponse {
+    track: TrackInfo,
+}
+
+/// A track similar to another one (`track.getSimilar`).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct SimilarTrack {
+    pub name: String,
+    pub artist: Artist,
+    pub url: String,
+    /// The MusicBrainz ID of the track (if known).
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub mbid: Option<String>,
+    #[serde(default)]
+    pub image: ImageSet,
+    /// The length of the track in seconds (`0` if unknown).
+    #[serde(default, deserialize_with = "number_from_any")]
+    pub duration: u32,
+    #[serde(default, deserialize_with = "number_from_any")]
+    pub playcount: u64,
+    /// How similar the track is, from `0.0` to `1.0`.
+    #[serde(rename = "match", deserialize_with = "number_from_any")]
+    pub match_score: f64,
+}
+
+/// The corrected version of a misspelled track (`track.getCorrection`).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "RawTrackCorrection")]
+pub struct TrackCorrection {
+    pub name: String,
+    pub artist: Artist,
+    pub url: String,
+    /// The MusicBrainz ID of the track (if known).
+    pub mbid: Option<String>,
+    /// Whether the artist name was corrected.
+    pub artist_corrected: bool,
+    /// Whether the track name was corrected.
+    pub track_corrected: bool,
+}
+
+#[derive(Deserialize)]
+struct RawTrackCorrection {
+    track: RawCorrectedTrack,
+    #[serde(rename = "@attr")]
+    attr: RawCorrectionAttr,
+}
+
+#[derive(Deserialize)]
+struct RawCorrectedTrack {
+    name: String,
+    artist: Artist,
+    #[serde(default)]
+    url: String,
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    mbid: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawCorrectionAttr {
+    #[serde(default, deserialize_with = "bool_from_any")]
+    artistcorrected: bool,
+    #[serde(default, deserialize_with = "bool_from_any")]
+    trackcorrected: bool,
+}
+
+impl From<RawTrackCorrection> for TrackCorrection {
+    fn from(raw: RawTrackCorrection) -> Self {
+        TrackCorrection {
+            name: raw.track.name,
+            artist: raw.track.artist,
+            url: raw.track.url,
+            mbid: raw.track.mbid,
+            artist_corrected: raw.attr.artistcorrected,
+            track_corrected: raw.attr.trackcorrected,
+        }
+    }
+}
+
+impl<A: AsRef<str>, U: AsRef<str>> Client<A, U> {
+    /// Fetches the metadata of a track (`track.getInfo`).
+    ///
+    /// [`TrackInfo::user_playcount`] and [`TrackInfo::user_loved`] are only available when a user is set
+    /// with [`TrackQuery::username`].
+    pub async fn track_info(&self, query: &TrackQuery) -> Result<TrackInfo, Error> {
+        let params = query.info_params();
+        let res: TrackInfoResponse = self
+            .transport()
+            .get("track.getInfo", params, Auth::None)
+            .await?;
+
+        Ok(res.track)
+    }
+
+    /// Fetches up to `limit` tracks similar to the given one, most similar first (`track.getSimilar`).
+    pub async fn similar_tracks(
+        &self,
+        query: &TrackQuery,
+        limit: u32,
+    ) -> Result<Vec<SimilarTrack>, Error> {
+        let params = query.params().with("limit", limit);
+        let raw: Value = self
+            .transport()
+            .get("track.getSimilar", params, Auth::None)
+            .await?;
+
+        Ok(items_under(&raw, "similartracks", "track")?)
+    }
+
+    /// Fetches the top tags of a track (`track.getTopTags`).
+    pub async fn track_top_tags(&self, query: &TrackQuery) -> Result<Vec<Tag>, Error> {
+        let raw: Value = self
+            .transport()
+            .get("track.getTopTags", query.params(), Auth::None)
+            .await?;
+
+        Ok(items_under(&raw, "toptags", "tag")?)
+    }
+
+    /// Checks whether the given artist and track names have a correction in Last.fm
+    /// (`track.getCorrection`), e.g. to normalise misspelled scrobbles.
+    ///
+    /// Returns `None` if Last.fm does not know the track.
+    pub async fn track_correction(
+        &self,
+        artist: &str,
+        track: &str,
+    ) -> Result<Option<TrackCorrection>, Error> {
+        let params = Params::new().with("artist", artist).with("track", track);
+        let raw: Value = self
+            .transport()
+            .get("track.getCorrection", params, Auth::None)
+            .await?;
+
+        Ok(parse_correction(&raw)?)
+    }
+}
+
+/// Extracts the first correction (if any) of a `track.getCorrection` response.
+///
+/// When there is no correction, Last.fm returns a blank string instead of the `corrections` object.
+fn parse_correction(raw: &Value) -> Result<Option<TrackCorrection>, serde_json::Error> {
+    match raw.get("corrections").and_then(|c| c.get("correction")) {
+        Some(Value::Array(corrections)) => corrections
+            .first()
+            .map(|c| serde_json::from_value(c.clone()))
+            .transpose(),
+        Some(correction) => serde_json::from_value(correction.clone()).map(Some),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn it_deserializes_track_info() {
+        let json_data = include_str!("fixtures/track_info.json");
+        let res: TrackInfoResponse = serde_json::from_str(json_data).unwrap();
+        insta::assert_debug_snapshot!(res.track);
+    }
+
+    #[test]
+    fn it_deserializes_similar_tracks() {
+        let raw = json!({
+          "similartracks": {
+            "track": {
+              "name": "Cornflakes",
+              "playcount": 202134,
+              "mbid": "",
+              "match": 0.72,
+              "url": "https://www.last.fm/music/Comete/_/Cornflakes",
+              "streamable": { "#text": "0", "fulltrack": "0" },
+              "duration": 201,
+              "artist": { "name": "Comete", "mbid": "", "url": "https://www.last.fm/music/Comete" },
+              "image": []
+            },
+            "@attr": { "artist": "Comete" }
+          }
+        });
+
+        let similar: Vec<SimilarTrack> = items_under(&raw, "similartracks", "track").unwrap();
+        assert_eq!(similar.len(), 1);
+        assert_eq!(similar[0].name, "Cornflakes");
+        assert_eq!(similar[0].duration, 201);
+        assert_eq!(similar[0].match_score, 0.72);
+    }
+
+    #[test]
+    fn it_parses_a_correction() {
+        let raw = json!({
+          "corrections": {
+            "correction": {
+              "track": {
+                "name": "Antenna",
+                "mbid": "",
+                "url": "https://www.last.fm/music/Comete/_/Antenna",
+                "artist": { "name": "Comete", "mbid": "", "url": "https://www.last.fm/music/Comete" }
+              },
+              "@attr": { "index": "0", "artistcorrected": "1", "trackcorrected": "1" }
+            }
+          }
+        });
+
+        let correction = parse_correction(&raw).unwrap().unwrap();
+        assert_eq!(correction.name, "Antenna");
+        assert_eq!(correction.artist.name, "Comete");
+        assert!(correction.artist_corrected);
+        assert!(correction.track_corrected);
+
+        let raw = json!({ "corrections": "\n  " });
+        assert_eq!(parse_correction(&raw).unwrap(), None);
+    }
+}
@@ -0,0 +1,96 @@
+//! # Track scrobbles
+//!
+//! Defines the [`TrackScrobblesFetcher`] struct, used to get every scrobble of a single track.
+//!
+//! ```rust,no_run
+//! use futures_util::pin_mut;
+//! use futures_util::stream::StreamExt;
+//! # use lastfm::Client;
+//! #
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!   let client = Client::builder().api_key("YOUR_API_KEY").username("YOUR_USERNAME").build();
+//!   let scrobbles = client.track_scrobbles("Comete", "Antenna").await?;
+//!   println!("Total scrobbles: {}", scrobbles.total_tracks);
+//!
+//!   let scrobbles = scrobbles.into_stream();
+//!   pin_mut!(scrobbles);
+//!   while let Some(track) = scrobbles.next().await {
+//!     println!("{}", track?.date.to_rfc2822());
+//!   }
+//!
+//!   Ok(())
+//! }
+//! ```
+use crate::{
+    errors::Error,
+    pagination::{PageFetcher, PageRequest},
+    request::Params,
+    track::{RecordedTrack, Track},
+    Client,
+};
+use tokio_stream::{Stream, StreamExt};
+
+/// Structs that can be used to get a stream of the [`RecordedTrack`]s of a single track
+/// (most recent first).
+#[non_exhaustive]
+pub struct TrackScrobblesFetcher {
+    fetcher: PageFetcher<Track>,
+    /// The total number of tracks available in the stream.
+    pub total_tracks: u64,
+}
+
+impl TrackScrobblesFetcher {
+    /// Converts the current instance into a stream of [`RecordedTrack`]s.
+    pub fn into_stream(self) -> impl Stream<Item = Result<RecordedTrack, Error>> {
+        self.fetcher.into_stream().filter_map(|track| match track {
+            Ok(Track::Recorded(t)) => Some(Ok(t)),
+            Ok(Track::NowPlaying(_)) => None,
+            Err(e) => Some(Err(e)),
+        })
+    }
+}
+
+impl<A: AsRef<str>, U: AsRef<str>> Client<A, U> {
+    /// Creates a new [`TrackScrobblesFetcher`] that can be used to fetch all the times the user
+    /// scrobbled the given track (`user.getTrackScrobbles`).
+    pub async fn track_scrobbles(
+        &self,
+        artist: &str,
+        track: &str,
+    ) -> Result<TrackScrobblesFetcher, Error> {
+        let fetcher = PageFetcher::new(
+            self.transport(),
+            PageRequest {
+                method: "user.getTrackScrobbles",
+                params: Params::new()
+                    .with("user", self.username())
+                    .with("artist", artist)
+                    .with("track", track)
+                    .with("limit", 200),
+                root: "trackscrobbles",
+                item: "track",
+            },
+        )
+        .await?;
+
+        Ok(TrackScrobblesFetcher {
+            total_tracks: fetcher.total,
+            fetcher,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{serde_helpers::items_under, track::Track};
+
+    #[test]
+    fn it_deserializes_track_scrobbles() {
+        let json_data = include_str!("fixtures/track_scrobbles_page.json");
+        let raw: serde_json::Value = serde_json::from_str(json_data).unwrap();
+
+        let tracks: Vec<Track> = items_under(&raw, "trackscrobbles", "track").unwrap();
+        insta::assert_debug_snapshot!(tracks);
+    }
+}
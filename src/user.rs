@@ -0,0 +1,228 @@
+//! # User
+//!
+//! Defines the [`UserProfile`] struct and the methods to fetch information about a Last.fm user
+//! and their friends.
+use crate::{
+    client::get_now_playing,
+    errors::Error,
+    imageset::ImageSet,
+    lfm_date::LfmDate,
+    pagination::{PageFetcher, PageRequest},
+    request::{Auth, Params},
+    serde_helpers::{bool_from_any, empty_string_as_none, number_from_any},
+    track::NowPlayingTrack,
+    Client,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::{sync::Semaphore, task::JoinSet};
+use tokio_stream::StreamExt;
+
+/// The profile of a Last.fm user.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UserProfile {
+    pub name: String,
+    #[serde(
+        rename = "realname",
+        default,
+        deserialize_with = "empty_string_as_none"
+    )]
+    pub real_name: Option<String>,
+    pub url: String,
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub country: Option<String>,
+    /// When the user signed up to Last.fm.
+    pub registered: LfmDate,
+    /// The total number of scrobbles of the user.
+    #[serde(deserialize_with = "number_from_any")]
+    pub playcount: u64,
+    /// The number of distinct artists the user listened to.
+    #[serde(default, deserialize_with = "number_from_any")]
+    pub artist_count: u64,
+    /// The number of distinct tracks the user listened to.
+    #[serde(default, deserialize_with = "number_from_any")]
+    pub track_count: u64,
+    /// The number of distinct albums the user listened to.
+    #[serde(default, deserialize_with = "number_from_any")]
+    pub album_count: u64,
+    /// Whether the user is a Last.fm subscriber.
+    #[serde(deserialize_with = "bool_from_any")]
+    pub subscriber: bool,
+    #[serde(default)]
+    pub image: ImageSet,
+}
+
+/// The raw response of the `user.getInfo` method.
+#[derive(Deserialize)]
+struct UserInfoResponse {
+    user: UserProfile,
+}
+
+/// Structs that can be used to get a stream of the user's friends.
+pub type FriendsFetcher = PageFetcher<UserProfile>;
+
+/// The track a friend is currently playing.
+#[derive(Debug)]
+pub struct FriendNowPlaying {
+    pub friend: UserProfile,
+    /// The track the friend is currently playing (if any), or the error raised while fetching it.
+    pub now_playing: Result<Option<NowPlayingTrack>, Error>,
+}
+
+impl<A: AsRef<str>, U: AsRef<str>> Client<A, U> {
+    /// Fetches the profile of the user (`user.getInfo`).
+    ///
+    /// The registration date can be used to bound a full history sync
+    /// (e.g. with [`Client::recent_tracks`]).
+    pub async fn user_info(&self) -> Result<UserProfile, Error> {
+        let params = Params::new().with("user", self.username());
+        let res: UserInfoResponse = self
+            .transport()
+            .get("user.getInfo", params, Auth::None)
+            .await?;
+
+        Ok(res.user)
+    }
+
+    /// Creates a new [`FriendsFetcher`] that can be used to fetch all of the user's friends (`user.getFriends`).
+    pub async fn friends(&self) -> Result<FriendsFetcher, Error> {
+        PageFetcher::new(
+            self.transport(),
+            PageRequest {
+                method: "user.getFriends",
+                params: Params::new()
+                    .with("user", self.username())
+                    .with("limit", 200),
+                root: "friends",
+                item: "user",
+            },
+        )
+        .await
+    }
+
+    /// Fetches the track every friend of the user is currently playing.
+    ///
+    /// Requests are sent concurrently, with at most `max_concurrency` requests in flight at any time.
+    /// Results are returned in the same order as [`Client::friends`]. Failing to fetch the list of friends
+    /// fails the whole call, while a failure for a single friend is reported in [`FriendNowPlaying::now_playing`].
+    pub async fn friends_now_playing(
+        &self,
+        max_concurrency: usize,
+    ) -> Result<Vec<FriendNowPlaying>, Error> {
+        let friends: Vec<UserProfile> = self
+            .friends()
+            .await?
+            .into_stream()
+            .collect::<Result<_, _>>()
+            .await?;
+
+        let semaphore = Arc::new(Semaphore::new(max_concurrency.max(1)));
+        let mut tasks = JoinSet::new();
+        for (index, friend) in friends.iter().enumerate() {
+            let semaphore = semaphore.clone();
+            let transport = self.transport();
+            let username = friend.name.clone();
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let now_playing = get_now_playing(&transport, &username).await;
+                (index, now_playing)
+            });
+        }
+
+        let results = join_in_order(tasks, friends.len()).await;
+
+        Ok(friends
+            .into_iter()
+            .zip(results)
+            .map(|(friend, now_playing)| FriendNowPlaying {
+                friend,
+                now_playing,
+            })
+            .collect())
+    }
+}
+
+/// Waits for all the tasks of the set, each returning its result with its index,
+/// and returns the results in index order.
+///
+/// If a task panics, the panic is propagated to the caller.
+/// Tasks are never aborted, so every task either completes or panics.
+async fn join_in_order<T: 'static>(
+    mut tasks: JoinSet<(usize, Result<T, Error>)>,
+    len: usize,
+) -> Vec<Result<T, Error>> {
+    let mut results: Vec<Option<Result<T, Error>>> = (0..len).map(|_| None).collect();
+    while let Some(res) = tasks.join_next().await {
+        match res {
+            Ok((index, result)) => results[index] = Some(result),
+            Err(e) => std::panic::resume_unwind(e.into_panic()),
+        }
+    }
+
+    results
+        .into_iter()
+        .map(|result| result.expect("every task completed"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn it_joins_tasks_in_order() {
+        let mut tasks = JoinSet::new();
+        tasks.spawn(async {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            (0, Ok("first"))
+        });
+        tasks.spawn(async { (1, Ok("second")) });
+        tasks.spawn(async { (2, Err(Error::MissingSessionKey)) });
+
+        let results = join_in_order(tasks, 3).await;
+        assert_eq!(results[0].as_ref().unwrap(), &"first");
+        assert_eq!(results[1].as_ref().unwrap(), &"second");
+        assert!(matches!(results[2], Err(Error::MissingSessionKey)));
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "boom")]
+    async fn it_propagates_task_panics() {
+        let mut tasks: JoinSet<(usize, Result<(), Error>)> = JoinSet::new();
+        tasks.spawn(async { panic!("boom") });
+
+        join_in_order(tasks, 1).await;
+    }
+
+    #[test]
+    fn it_deserializes_a_user_profile() {
+        let json_data = include_str!("fixtures/user_info.json");
+
+        let res: UserInfoResponse = serde_json::from_str(json_data).unwrap();
+        insta::assert_debug_snapshot!(res.user);
+    }
+
+    #[test]
+    fn it_deserializes_a_friend() {
+        let json_value = serde_json::json!({
+          "name": "eric",
+          "url": "https://www.last.fm/user/eric",
+          "country": "",
+          "playlists": "0",
+          "playcount": "5012",
+          "image": [],
+          "registered": { "unixtime": "1164063538", "#text": "2006-11-20 22:58" },
+          "realname": "",
+          "subscriber": "1",
+          "bootstrap": "0",
+          "type": "user"
+        });
+
+        let friend: UserProfile = serde_json::from_value(json_value).unwrap();
+        assert_eq!(friend.name, "eric");
+        assert_eq!(friend.real_name, None);
+        assert_eq!(friend.country, None);
+        assert_eq!(friend.artist_count, 0);
+        assert!(friend.subscriber);
+    }
+}
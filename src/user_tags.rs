@@ -0,0 +1,194 @@
+//! # User tags
+//!
+//! Defines the [`TaggedAlbum`] and [`TaggedTrack`] structs and the methods to fetch the user's
+//! top tags and the artists, albums and tracks the user tagged with a given tag.
+//!
+//! ```rust,no_run
+//! use futures_util::pin_mut;
+//! use futures_util::stream::StreamExt;
+//! # use lastfm::Client;
+//! #
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!   let client = Client::builder().api_key("YOUR_API_KEY").username("YOUR_USERNAME").build();
+//!   let tags = client.top_tags().await?.into_stream();
+//!   pin_mut!(tags);
+//!   while let Some(tag) = tags.next().await {
+//!     let tag = tag?;
+//!     println!("{} ({} times)", tag.name, tag.count);
+//!   }
+//!
+//!   let artists = client.personal_tagged_artists("rock").await?.into_stream();
+//!   pin_mut!(artists);
+//!   while let Some(artist) = artists.next().await {
+//!     println!("{}", artist?.name);
+//!   }
+//!
+//!   Ok(())
+//! }
+//! ```
+use crate::{
+    artist::Artist,
+    errors::Error,
+    imageset::ImageSet,
+    pagination::{PageFetcher, PageRequest},
+    request::Params,
+    serde_helpers::{empty_string_as_none, number_from_any},
+    tag::Tag,
+    Client,
+};
+use serde::{Deserialize, Serialize};
+
+/// An album tagged by the user.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TaggedAlbum {
+    pub name: String,
+    pub artist: Artist,
+    pub url: String,
+    /// The MusicBrainz ID of the album (if known).
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub mbid: Option<String>,
+    #[serde(default)]
+    pub image: ImageSet,
+}
+
+/// A track tagged by the user.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TaggedTrack {
+    pub name: String,
+    pub artist: Artist,
+    pub url: String,
+    /// The MusicBrainz ID of the track (if known).
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub mbid: Option<String>,
+    #[serde(default)]
+    pub image: ImageSet,
+    /// The length of the track in seconds (`0` if unknown).
+    #[serde(default, deserialize_with = "number_from_any")]
+    pub duration: u32,
+}
+
+/// Structs that can be used to get a stream of [`Tag`]s.
+pub type TagsFetcher = PageFetcher<Tag>;
+
+/// Structs that can be used to get a stream of the [`Artist`]s tagged by the user.
+pub type TaggedArtistsFetcher = PageFetcher<Artist>;
+
+/// Structs that can be used to get a stream of [`TaggedAlbum`]s.
+pub type TaggedAlbumsFetcher = PageFetcher<TaggedAlbum>;
+
+/// Structs that can be used to get a stream of [`TaggedTrack`]s.
+pub type TaggedTracksFetcher = PageFetcher<TaggedTrack>;
+
+impl<A: AsRef<str>, U: AsRef<str>> Client<A, U> {
+    /// Creates a new [`TagsFetcher`] that can be used to fetch the tags most used by the user
+    /// (`user.getTopTags`).
+    pub async fn top_tags(&self) -> Result<TagsFetcher, Error> {
+        PageFetcher::new(
+            self.transport(),
+            PageRequest {
+                method: "user.getTopTags",
+                params: Params::new()
+                    .with("user", self.username())
+                    .with("limit", 200),
+                root: "toptags",
+                item: "tag",
+            },
+        )
+        .await
+    }
+
+    /// Builds the request for `user.getPersonalTags` with the given tagging type.
+    fn personal_tags_request(
+        &self,
+        tag: &str,
+        tagging_type: &'static str,
+        item: &'static str,
+    ) -> PageRequest {
+        PageRequest {
+            method: "user.getPersonalTags",
+            params: Params::new()
+                .with("user", self.username())
+                .with("tag", tag)
+                .with("taggingtype", tagging_type)
+                .with("limit", 200),
+            root: "taggings",
+            item,
+        }
+    }
+
+    /// Creates a new [`TaggedArtistsFetcher`] that can be used to fetch the artists the user tagged
+    /// with the given tag (`user.getPersonalTags`).
+    pub async fn personal_tagged_artists(&self, tag: &str) -> Result<TaggedArtistsFetcher, Error> {
+        let request = self.personal_tags_request(tag, "artist", "artists.artist");
+        PageFetcher::new(self.transport(), request).await
+    }
+
+    /// Creates a new [`TaggedAlbumsFetcher`] that can be used to fetch the albums the user tagged
+    /// with the given tag (`user.getPersonalTags`).
+    pub async fn personal_tagged_albums(&self, tag: &str) -> Result<TaggedAlbumsFetcher, Error> {
+        let request = self.personal_tags_request(tag, "album", "albums.album");
+        PageFetcher::new(self.transport(), request).await
+    }
+
+    /// Creates a new [`TaggedTracksFetcher`] that can be used to fetch the tracks the user tagged
+    /// with the given tag (`user.getPersonalTags`).
+    pub async fn personal_tagged_tracks(&self, tag: &str) -> Result<TaggedTracksFetcher, Error> {
+        let request = self.personal_tags_request(tag, "track", "tracks.track");
+        PageFetcher::new(self.transport(), request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serde_helpers::items_under;
+    use serde_json::json;
+
+    #[test]
+    fn it_deserializes_top_tags() {
+        let raw = json!({
+          "toptags": {
+            "tag": [
+              { "name": "rock", "count": 12, "url": "https://www.last.fm/tag/rock" },
+              { "name": "indie", "count": 3, "url": "https://www.last.fm/tag/indie" }
+            ],
+            "@attr": { "user": "loige" }
+          }
+        });
+
+        let tags: Vec<Tag> = items_under(&raw, "toptags", "tag").unwrap();
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].name, "rock");
+        assert_eq!(tags[1].count, 3);
+    }
+
+    #[test]
+    fn it_deserializes_personal_tagged_tracks() {
+        let raw = json!({
+          "taggings": {
+            "tracks": {
+              "track": {
+                "name": "Antenna",
+                "duration": "180",
+                "mbid": "",
+                "url": "https://www.last.fm/music/Comete/_/Antenna",
+                "streamable": { "#text": "0", "fulltrack": "0" },
+                "artist": {
+                  "name": "Comete",
+                  "mbid": "",
+                  "url": "https://www.last.fm/music/Comete"
+                },
+                "image": [
+                  { "#text": "https://lastfm.freetls.fastly.net/i/u/34s/2a96cbd8b46e442fc41c2b86b821562f.png", "size": "small" }
+                ]
+              }
+            },
+            "@attr": { "user": "loige", "tag": "indie", "page": "1", "perPage": "50", "totalPages": "1", "total": "1" }
+          }
+        });
+
+        let tracks: Vec<TaggedTrack> = items_under(&raw, "taggings", "tracks.track").unwrap();
+        insta::assert_debug_snapshot!(tracks);
+    }
+}
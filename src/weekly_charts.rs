@@ -0,0 +1,155 @@
+//! # Weekly charts
+//!
+//! Defines the [`ChartRange`] struct and the methods to fetch the user's historical weekly charts.
+//!
+//! Weekly charts are only available for the ranges returned by [`Client::weekly_chart_list`].
+//!
+//! ```rust,no_run
+//! # use lastfm::Client;
+//! #
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!   let client = Client::builder().api_key("YOUR_API_KEY").username("YOUR_USERNAME").build();
+//!   let ranges = client.weekly_chart_list().await?;
+//!   if let Some(last_week) = ranges.last() {
+//!     for artist in client.weekly_artist_chart(last_week).await? {
+//!       println!("{}. {} ({} plays)", artist.rank, artist.artist.name, artist.playcount);
+//!     }
+//!   }
+//!
+//!   Ok(())
+//! }
+//! ```
+use crate::{
+    errors::Error,
+    request::{Auth, Params},
+    serde_helpers::{datetime_from_uts, items_under},
+    top::{TopAlbum, TopArtist, TopTrack},
+    Client,
+};
+use chrono::{DateTime, Utc};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+/// The time range covered by a weekly chart.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChartRange {
+    #[serde(deserialize_with = "datetime_from_uts")]
+    pub from: DateTime<Utc>,
+    #[serde(deserialize_with = "datetime_from_uts")]
+    pub to: DateTime<Utc>,
+}
+
+impl From<ChartRange> for (DateTime<Utc>, DateTime<Utc>) {
+    fn from(range: ChartRange) -> Self {
+        (range.from, range.to)
+    }
+}
+
+impl<A: AsRef<str>, U: AsRef<str>> Client<A, U> {
+    /// Fetches the list of ranges for which the user's weekly charts are available (`user.getWeeklyChartList`),
+    /// from the oldest to the most recent.
+    pub async fn weekly_chart_list(&self) -> Result<Vec<ChartRange>, Error> {
+        let params = Params::new().with("user", self.username());
+        let raw: Value = self
+            .transport()
+            .get("user.getWeeklyChartList", params, Auth::None)
+            .await?;
+
+        Ok(items_under(&raw, "weeklychartlist", "chart")?)
+    }
+
+    /// Fetches one of the `user.getWeekly*Chart` charts for the given range.
+    async fn weekly_chart<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        root: &str,
+        item: &str,
+        range: &ChartRange,
+    ) -> Result<Vec<T>, Error> {
+        let params = Params::new()
+            .with("user", self.username())
+            .with("from", range.from.timestamp())
+            .with("to", range.to.timestamp());
+        let raw: Value = self.transport().get(method, params, Auth::None).await?;
+
+        Ok(items_under(&raw, root, item)?)
+    }
+
+    /// Fetches the user's artist chart for the given range (`user.getWeeklyArtistChart`).
+    pub async fn weekly_artist_chart(&self, range: &ChartRange) -> Result<Vec<TopArtist>, Error> {
+        self.weekly_chart(
+            "user.getWeeklyArtistChart",
+            "weeklyartistchart",
+            "artist",
+            range,
+        )
+        .await
+    }
+
+    /// Fetches the user's album chart for the given range (`user.getWeeklyAlbumChart`).
+    pub async fn weekly_album_chart(&self, range: &ChartRange) -> Result<Vec<TopAlbum>, Error> {
+        self.weekly_chart(
+            "user.getWeeklyAlbumChart",
+            "weeklyalbumchart",
+            "album",
+            range,
+        )
+        .await
+    }
+
+    /// Fetches the user's track chart for the given range (`user.getWeeklyTrackChart`).
+    pub async fn weekly_track_chart(&self, range: &ChartRange) -> Result<Vec<TopTrack>, Error> {
+        self.weekly_chart(
+            "user.getWeeklyTrackChart",
+            "weeklytrackchart",
+            "track",
+            range,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn it_deserializes_a_chart_list() {
+        let raw = json!({
+          "weeklychartlist": {
+            "chart": [
+              { "#text": "", "from": "1108296000", "to": "1108900800" },
+              { "#text": "", "from": "1108900800", "to": "1109505600" }
+            ],
+            "@attr": { "user": "loige" }
+          }
+        });
+
+        let ranges: Vec<ChartRange> = items_under(&raw, "weeklychartlist", "chart").unwrap();
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(ranges[0].from.timestamp(), 1108296000);
+        assert_eq!(ranges[1].to.timestamp(), 1109505600);
+    }
+
+    #[test]
+    fn it_deserializes_a_weekly_album_chart() {
+        let raw = json!({
+          "weeklyalbumchart": {
+            "album": {
+              "artist": { "mbid": "", "#text": "Comete" },
+              "mbid": "",
+              "url": "https://www.last.fm/music/Comete/Solo+cose+belle",
+              "name": "Solo cose belle",
+              "@attr": { "rank": "1" },
+              "playcount": "12"
+            },
+            "@attr": { "from": "1108296000", "user": "loige", "to": "1108900800" }
+          }
+        });
+
+        let albums: Vec<TopAlbum> = items_under(&raw, "weeklyalbumchart", "album").unwrap();
+        insta::assert_debug_snapshot!(albums);
+    }
+}
@@ -0,0 +1,18 @@
+//! # Wiki
+//!
+//! defines the [`Wiki`] struct, the biography or description attached to artists, albums, tracks and tags.
+use serde::{Deserialize, Serialize};
+
+/// The wiki (or biography) of a Last.fm artist, album, track or tag.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Wiki {
+    /// When the wiki was published, as formatted by Last.fm (e.g. `01 Jan 2010, 00:00`).
+    #[serde(default)]
+    pub published: String,
+    /// A short summary (HTML).
+    #[serde(default)]
+    pub summary: String,
+    /// The full content (HTML).
+    #[serde(default)]
+    pub content: String,
+}